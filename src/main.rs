@@ -5,8 +5,134 @@
 mod ui;
 mod wordle;
 
+use std::time::Duration;
 use ui::cli;
-use wordle::{analyzer::StartingWordAnalyzer, solver};
+use wordle::{
+    absurdle::AbsurdleSearch,
+    adversary::{AdversarialHost, TieBreak},
+    analyzer::StartingWordAnalyzer,
+    benchmark::{benchmark, BenchmarkResult},
+    book::OpeningBook,
+    core::{load_words, load_words_from_file, EMBEDDED_WORD_LEN, MAX_WORD_LEN, MIN_WORD_LEN},
+    dictionary::Dictionary,
+    prior::{load_frequencies, Prior},
+    solver::{
+        self, EntropyMaximizer, LookaheadSolver, MctsSolver, ProbePolicy, ProbeSampling,
+        SolverStrategy, SurvivalSolver, TreeSolver,
+    },
+    tree::{DecisionTree, TreeSearch, MAX_DEPTH},
+};
+
+/// Answers from `--dict` if given, else the embedded list (5 letters only), and
+/// allowed guesses from `--guesses` if given.
+fn load_dictionary(
+    answers_path: Option<&str>,
+    guesses_path: Option<&str>,
+    word_len: usize,
+) -> Result<Dictionary, String> {
+    let load = |path: &str| {
+        load_words_from_file(path, word_len).map_err(|e| format!("Could not load {}: {}", path, e))
+    };
+    let answers = match answers_path {
+        Some(path) => load(path)?,
+        None if word_len == EMBEDDED_WORD_LEN => {
+            load_words().map_err(|e| format!("Could not load embedded words: {}", e))?
+        }
+        None => {
+            return Err(format!(
+                "No embedded {}-letter dictionary; pass one with --dict PATH",
+                word_len
+            ))
+        }
+    };
+    let guesses = match guesses_path {
+        Some(path) => load(path)?,
+        None => Vec::new(),
+    };
+    Ok(Dictionary::cached(answers, guesses))
+}
+
+/// Tuning flags for the strategies that take them.
+struct StrategyOptions {
+    top_k: usize,
+    depth: usize,
+    /// None runs `MctsSolver::DEFAULT_ITERATIONS`, or until the time limit
+    /// when one is given.
+    iterations: Option<usize>,
+    time_limit: Option<Duration>,
+    seed: u64,
+    probes: ProbePolicy,
+}
+
+impl Default for StrategyOptions {
+    fn default() -> Self {
+        StrategyOptions {
+            top_k: LookaheadSolver::DEFAULT_TOP_K,
+            depth: LookaheadSolver::DEFAULT_DEPTH,
+            iterations: None,
+            time_limit: None,
+            seed: MctsSolver::DEFAULT_SEED,
+            probes: ProbePolicy::default(),
+        }
+    }
+}
+
+/// The strategy named on the command line, tuned by `options`.
+fn build_solver(
+    strategy: &str,
+    options: &StrategyOptions,
+    progress: bool,
+) -> Box<dyn SolverStrategy> {
+    match strategy {
+        "entropy" => Box::new(EntropyMaximizer::new(options.probes)),
+        "lookahead" => Box::new(
+            LookaheadSolver::new(options.top_k, options.depth)
+                .with_progress(progress)
+                .with_probes(options.probes),
+        ),
+        "survival" => Box::new(SurvivalSolver::default().with_probes(options.probes)),
+        "mcts" => {
+            let iterations = match (options.iterations, options.time_limit) {
+                (Some(n), _) => n,
+                (None, Some(_)) => usize::MAX,
+                (None, None) => MctsSolver::DEFAULT_ITERATIONS,
+            };
+            let mcts = MctsSolver::new(iterations).with_seed(options.seed);
+            match options.time_limit {
+                Some(limit) => Box::new(mcts.with_time_limit(limit)),
+                None => Box::new(mcts),
+            }
+        }
+        _ => solver::create_solver(strategy),
+    }
+}
+
+fn strategy_name(strategy: &str) -> &'static str {
+    match strategy {
+        "simple" => "Simple",
+        "frequency" => "Frequency Analyzer",
+        "frequency-probes" => "Frequency Analyzer with probe words",
+        "minimax" => "Minimax (worst case)",
+        "lookahead" => "Lookahead (expected guesses)",
+        "survival" => "Survival (solve within 6)",
+        "absurdle" => "Absurdle (fewest guaranteed guesses)",
+        "mcts" => "Monte Carlo tree search",
+        "tree" => "Decision tree",
+        _ => "Entropy Maximizer (optimal)",
+    }
+}
+
+fn print_benchmark(name: &str, result: &BenchmarkResult) {
+    println!(
+        "{:<36} {:>6.3} {:>5} {:>5}/{:<5} {:>4}",
+        name,
+        result.average(),
+        result.max_guesses(),
+        result.solved_within(6),
+        result.games,
+        result.failures.len()
+    );
+}
 
 fn main() {
     use std::env;
@@ -17,14 +143,124 @@ fn main() {
     let mut strategy = "entropy";
     let mut analyze_starters = false;
     let mut analyze_word: Option<String> = None;
+    let mut word_len = EMBEDDED_WORD_LEN;
+    let mut dict_path: Option<String> = None;
+    let mut guesses_path: Option<String> = None;
+    let mut freq_path: Option<String> = None;
+    let mut reverse_answer: Option<String> = None;
+    let mut hard_mode = false;
+    let mut run_benchmark = false;
+    let mut options = StrategyOptions::default();
+    let mut probe_sampling = "coverage".to_string();
+    let mut build_tree_opener: Option<String> = None;
+    let mut book_path: Option<String> = None;
+    let mut build_book_path: Option<String> = None;
+    let mut tree_out = "wordle-tree.json".to_string();
+    let mut tree_width: Option<usize> = None;
+    let mut max_depth = MAX_DEPTH;
+    let mut tree_path: Option<String> = None;
+    let mut tie_break = TieBreak::default();
+    let mut solve_absurdle = false;
 
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
             "--simple" => strategy = "simple",
             "--entropy" => strategy = "entropy",
+            "--minimax" => strategy = "minimax",
             "--frequency" => strategy = "frequency",
+            "--frequency-probes" => strategy = "frequency-probes",
+            "--lookahead" => strategy = "lookahead",
+            "--survival" => strategy = "survival",
+            "--absurdle" => strategy = "absurdle",
+            "--solve-absurdle" => solve_absurdle = true,
+            "--mcts" => strategy = "mcts",
+            "--benchmark" => run_benchmark = true,
+            "--build-tree" | "--tree-out" | "--tree" | "--book" | "--build-book" => {
+                if i + 1 < args.len() {
+                    let value = args[i + 1].clone();
+                    match args[i].as_str() {
+                        "--build-tree" => build_tree_opener = Some(value),
+                        "--book" => book_path = Some(value),
+                        "--build-book" => build_book_path = Some(value),
+                        "--tree-out" => tree_out = value,
+                        _ => {
+                            tree_path = Some(value);
+                            strategy = "tree";
+                        }
+                    }
+                    i += 1;
+                } else {
+                    eprintln!("Error: {} requires an argument", args[i]);
+                    return;
+                }
+            }
+            "--tree-width" | "--max-depth" => {
+                match args.get(i + 1).and_then(|n| n.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => {
+                        if args[i] == "--tree-width" {
+                            tree_width = Some(n);
+                        } else {
+                            max_depth = n;
+                        }
+                        i += 1;
+                    }
+                    _ => {
+                        eprintln!("Error: {} requires a positive number", args[i]);
+                        return;
+                    }
+                }
+            }
+            "--top-k" | "--depth" | "--iterations" | "--time-ms" | "--seed" => {
+                match args.get(i + 1).and_then(|n| n.parse::<u64>().ok()) {
+                    Some(n) if n > 0 || args[i] == "--seed" => {
+                        match args[i].as_str() {
+                            "--top-k" => options.top_k = n as usize,
+                            "--depth" => options.depth = n as usize,
+                            "--iterations" => options.iterations = Some(n as usize),
+                            "--time-ms" => options.time_limit = Some(Duration::from_millis(n)),
+                            _ => options.seed = n,
+                        }
+                        i += 1;
+                    }
+                    _ => {
+                        eprintln!("Error: {} requires a positive number", args[i]);
+                        return;
+                    }
+                }
+            }
+            "--probes" => match args.get(i + 1).map(String::as_str) {
+                Some(name @ ("full" | "random" | "coverage")) => {
+                    probe_sampling = name.to_string();
+                    i += 1;
+                }
+                _ => {
+                    eprintln!("Error: --probes requires full, random or coverage");
+                    return;
+                }
+            },
+            "--tie-break" => match args.get(i + 1).and_then(|name| TieBreak::parse(name)) {
+                Some(rule) => {
+                    tie_break = rule;
+                    i += 1;
+                }
+                None => {
+                    eprintln!("Error: --tie-break requires worst or expected");
+                    return;
+                }
+            },
+            "--probe-budget" => match args.get(i + 1).and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) => {
+                    options.probes.budget = Some(n);
+                    i += 1;
+                }
+                None => {
+                    eprintln!("Error: --probe-budget requires a number");
+                    return;
+                }
+            },
             "--analyze-starters" => analyze_starters = true,
+            "--hard" => hard_mode = true,
             "--analyze" => {
                 if i + 1 < args.len() {
                     analyze_word = Some(args[i + 1].clone());
@@ -34,42 +270,119 @@ fn main() {
                     return;
                 }
             }
+            "--reverse" => {
+                if i + 1 < args.len() {
+                    reverse_answer = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --reverse requires the answer word");
+                    return;
+                }
+            }
+            "--length" => match args.get(i + 1).and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&n) => {
+                    word_len = n;
+                    i += 1;
+                }
+                _ => {
+                    eprintln!(
+                        "Error: --length requires a number from {} to {}",
+                        MIN_WORD_LEN, MAX_WORD_LEN
+                    );
+                    return;
+                }
+            },
+            "--dict" => {
+                if i + 1 < args.len() {
+                    dict_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --dict requires a file path");
+                    return;
+                }
+            }
+            "--freq" => {
+                if i + 1 < args.len() {
+                    freq_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --freq requires a file path");
+                    return;
+                }
+            }
+            "--guesses" => {
+                if i + 1 < args.len() {
+                    guesses_path = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --guesses requires a file path");
+                    return;
+                }
+            }
             _ => {}
         }
         i += 1;
     }
 
+    // The seed may come after --probes on the command line.
+    options.probes.sampling = match probe_sampling.as_str() {
+        "full" => ProbeSampling::Full,
+        "random" => ProbeSampling::Random { seed: options.seed },
+        _ => ProbeSampling::Coverage,
+    };
+
+    let mut dict = match load_dictionary(dict_path.as_deref(), guesses_path.as_deref(), word_len) {
+        Ok(dict) => dict,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    if let Some(path) = freq_path {
+        match load_frequencies(&path) {
+            Ok(counts) => {
+                let prior = Prior::from_counts(dict.answers(), &counts);
+                dict = dict.with_prior(prior);
+            }
+            Err(e) => {
+                eprintln!("Error: Could not load {}: {}", path, e);
+                return;
+            }
+        }
+    }
+
     // Handle analysis modes
     if analyze_starters {
         println!("🔬 Analyzing word list to find best starting words...");
-        println!("This may take a minute...\n");
+        println!("(The first run builds the pattern table cache.)\n");
 
-        let analyzer = StartingWordAnalyzer::new().expect("Failed to load words");
+        let analyzer = StartingWordAnalyzer::from_dictionary(dict);
         let best = analyzer.find_best_starters(20);
 
         println!("\n📊 Top 20 Starting Words by Entropy:\n");
         println!("Rank  Word   Entropy  E[remaining]  P(win)");
         println!("─────────────────────────────────────────────");
 
-        for (i, (word, entropy, exp_rem, p_win)) in best.iter().enumerate() {
+        for (i, s) in best.iter().enumerate() {
             println!(
                 "{:3}   {}   {:.3}     {:.1}        {:.4}",
                 i + 1,
-                word,
-                entropy,
-                exp_rem,
-                p_win
+                s.word,
+                s.entropy,
+                s.expected_remaining,
+                s.p_win
             );
         }
 
-        println!("\n💡 Best overall starter: {}\n", best[0].0);
+        println!("\n💡 Best overall starter: {}\n", best[0].word);
         return;
     }
 
     if let Some(word) = analyze_word {
         println!("🔬 Analyzing '{}' as a starting word...\n", word);
 
-        let analyzer = StartingWordAnalyzer::new().expect("Failed to load words");
+        let num_answers = dict.num_answers();
+        let analyzer = StartingWordAnalyzer::from_dictionary(dict);
 
         if let Some((entropy, exp_rem, p_win, patterns)) = analyzer.analyze_word(&word) {
             println!("📊 Statistics for '{}':", word.to_uppercase());
@@ -78,9 +391,9 @@ fn main() {
             println!("  P(instant win): {:.4}\n", p_win);
 
             println!("🎯 Top 10 most common patterns:");
-            for (i, (pid, count)) in patterns.iter().take(10).enumerate() {
-                let pattern = wordle::analyzer::pattern_to_string(*pid);
-                let percentage = (*count as f64 / 2316.0) * 100.0;
+            for (i, (pattern, count)) in patterns.iter().take(10).enumerate() {
+                let pattern = wordle::analyzer::pattern_to_string(pattern);
+                let percentage = (*count as f64 / num_answers as f64) * 100.0;
                 println!(
                     "  {:2}. {} - {} times ({:.1}%)",
                     i + 1,
@@ -95,14 +408,183 @@ fn main() {
         return;
     }
 
+    if let Some(opener) = build_tree_opener {
+        println!(
+            "🌳 Building the {} tree for {} within {} guesses...",
+            match tree_width {
+                Some(_) => "best pruned",
+                None => "optimal",
+            },
+            opener.to_uppercase(),
+            max_depth
+        );
+        let mut search = TreeSearch::new(&dict, max_depth, tree_width).with_progress(true);
+        match search.build(&opener) {
+            Ok(tree) => {
+                println!(
+                    "✅ {} answers, {} guesses in total, {:.4} average",
+                    tree.answers,
+                    tree.total_guesses,
+                    tree.average()
+                );
+                match tree.save(&tree_out) {
+                    Ok(()) => println!("💾 Saved to {}", tree_out),
+                    Err(e) => eprintln!("Error: Could not write {}: {}", tree_out, e),
+                }
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        return;
+    }
+
+    if solve_absurdle {
+        println!(
+            "😈 Searching for the fewest guesses that corner the host over {} answers{}...",
+            dict.num_answers(),
+            if hard_mode { " in hard mode" } else { "" }
+        );
+        let mut search = AbsurdleSearch::new(&dict, hard_mode).with_progress(true);
+        let mut host = AdversarialHost::new(&dict, tie_break);
+        let Some((proven, _)) = search.solve(host.candidates()) else {
+            eprintln!("Error: No answers to corner");
+            return;
+        };
+        println!(
+            "✅ Proven minimal: {} guesses corner the host, whichever tied bucket it keeps",
+            proven
+        );
+        // The host's own tie-break may concede earlier than the worst case.
+        let line = search.play_out(&mut host);
+        debug_assert!(line.len() <= proven);
+        println!("\n🎲 Played against the host, won in {}:\n", line.len());
+        for (i, turn) in line.iter().enumerate() {
+            let left = if turn.feedback.is_all_green() {
+                String::new()
+            } else {
+                format!("  {} left", turn.left)
+            };
+            println!(
+                "  {}. {}  {:#}{}",
+                i + 1,
+                turn.guess.to_uppercase(),
+                turn.feedback,
+                left
+            );
+        }
+        return;
+    }
+
+    if let Some(path) = build_book_path {
+        println!("📖 Building the opening book...");
+        let analyzer = StartingWordAnalyzer::from_dictionary(dict);
+        let book = OpeningBook::build(&analyzer);
+        match book.save(&path) {
+            Ok(()) => println!("💾 Opener {} saved to {}", book.opener, path),
+            Err(e) => eprintln!("Error: Could not write {}: {}", path, e),
+        }
+        return;
+    }
+
+    // Strategies open from the book: the one given, else the cached one for
+    // these word lists, built on first use.
+    let book = match book_path.as_deref().map(OpeningBook::load) {
+        Some(Ok(book)) => book,
+        Some(Err(e)) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+        None => {
+            let (plain, book) = OpeningBook::load_or_build(dict);
+            dict = plain;
+            book
+        }
+    };
+    if book.opener.chars().count() != dict.word_len() {
+        eprintln!(
+            "Error: The book opens with {}, not a {}-letter word",
+            book.opener,
+            dict.word_len()
+        );
+        return;
+    }
+    let dict = dict.with_book(book);
+
+    let tree = match tree_path.as_deref().map(DecisionTree::load) {
+        Some(Ok(tree)) if tree.word_len == dict.word_len() => Some(tree),
+        Some(Ok(tree)) => {
+            eprintln!(
+                "Error: The tree is for {}-letter words, not {}",
+                tree.word_len,
+                dict.word_len()
+            );
+            return;
+        }
+        Some(Err(e)) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+        None => None,
+    };
+    // The tree strategy needs the loaded tree, so it is built here rather than by name.
+    let make_solver = |progress: bool| -> Box<dyn SolverStrategy> {
+        match &tree {
+            Some(tree) => Box::new(TreeSolver {
+                tree: tree.clone(),
+                probes: options.probes,
+            }),
+            None => build_solver(strategy, &options, progress),
+        }
+    };
+
+    if run_benchmark {
+        // Per-game progress only; per-guess progress would flood the terminal.
+        let solver = make_solver(false);
+        let answers: Vec<usize> = (0..dict.num_answers()).collect();
+        println!(
+            "🏁 Benchmarking over {} answers{}\n",
+            answers.len(),
+            if hard_mode { " in hard mode" } else { "" }
+        );
+
+        let mut results = Vec::new();
+        if strategy != "entropy" {
+            println!("{}:", strategy_name("entropy"));
+            let greedy = build_solver("entropy", &options, false);
+            results.push((
+                "entropy",
+                benchmark(&*greedy, &dict, &answers, hard_mode, true),
+            ));
+        }
+        println!("{}:", strategy_name(strategy));
+        results.push((
+            strategy,
+            benchmark(&*solver, &dict, &answers, hard_mode, true),
+        ));
+
+        println!(
+            "\n{:<36} {:>6} {:>5} {:>11} {:>4}",
+            "Strategy", "Avg", "Max", "Within 6", "Lost"
+        );
+        for (name, result) in &results {
+            print_benchmark(strategy_name(name), result);
+        }
+        return;
+    }
+
+    if let Some(answer) = reverse_answer {
+        cli::run_reverse_mode(&answer, &dict);
+        return;
+    }
+
     // Normal game modes
     println!("🎮 Wordle CLI");
     println!("=============\n");
     println!("Choose mode:");
     println!("1. Solver Mode - I'll help you solve a Wordle");
-    println!("2. Game Mode - Play Wordle with a known solution\n");
+    println!("2. Game Mode - Play Wordle with a known solution");
+    println!("3. Evil Mode - Play against a host that dodges your guesses\n");
 
-    print!("Enter choice (1 or 2): ");
+    print!("Enter choice (1-3): ");
     io::stdout().flush().unwrap();
 
     let mut choice = String::new();
@@ -112,71 +594,66 @@ fn main() {
 
     match choice.trim() {
         "1" => {
-            let solver = solver::create_solver(strategy);
-            println!(
-                "\n📊 Using {} strategy",
-                match strategy {
-                    "simple" => "Simple",
-                    "frequency" => "Frequency Analyzer",
-                    _ => "Entropy Maximizer (optimal)",
-                }
-            );
+            let solver = make_solver(true);
+            println!("\n📊 Using {} strategy", strategy_name(strategy));
 
-            cli::run_solver_mode(solver);
+            cli::run_solver_mode(solver, &dict, hard_mode);
         }
-        "2" => cli::run_game_mode(),
+        "2" => cli::run_game_mode(&dict, hard_mode),
+        "3" => cli::run_evil_mode(&dict, hard_mode, tie_break),
         _ => {
             println!("Invalid choice. Defaulting to Solver Mode.");
-            let solver = solver::create_solver(strategy);
-            println!(
-                "📊 Using {} strategy",
-                match strategy {
-                    "simple" => "Simple",
-                    "frequency" => "Frequency Analyzer",
-                    _ => "Entropy Maximizer (optimal)",
-                }
-            );
-            cli::run_solver_mode(solver);
+            let solver = make_solver(true);
+            println!("📊 Using {} strategy", strategy_name(strategy));
+            cli::run_solver_mode(solver, &dict, hard_mode);
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::wordle::{core::*, types::*};
+    use crate::wordle::{
+        absurdle::*, adversary::*, alphabet::*, analyzer::*, benchmark, book::*, candidates::*,
+        core::*, diagnose::*, dictionary::*, endgame::*, explain::*, knowledge::*, pattern::*,
+        prior::*, reverse::*, share::*, solver::*, table::*, tree::*, types::*,
+    };
+
+    fn pattern(s: &str) -> Pattern {
+        s.parse().unwrap()
+    }
 
     #[test]
     fn test_calculate_feedback_all_green() {
         let feedback = calculate_feedback("CRANE", "CRANE");
-        assert_eq!(feedback, vec!['G', 'G', 'G', 'G', 'G']);
+        assert_eq!(feedback, pattern("GGGGG"));
     }
 
     #[test]
     fn test_calculate_feedback_mixed() {
         let feedback = calculate_feedback("TRACE", "CRANE");
-        assert_eq!(feedback, vec!['B', 'G', 'G', 'Y', 'G']);
+        assert_eq!(feedback, pattern("BGGYG"));
     }
 
     #[test]
     fn test_calculate_feedback_duplicate_letters() {
         let feedback = calculate_feedback("LLAMA", "LEVEL");
-        assert_eq!(feedback, vec!['G', 'Y', 'B', 'B', 'B']);
+        assert_eq!(feedback, pattern("GYBBB"));
     }
 
     #[test]
     fn test_calculate_feedback_all_yellow() {
         let feedback = calculate_feedback("EABCD", "ABCDE");
-        assert_eq!(feedback, vec!['Y', 'Y', 'Y', 'Y', 'Y']);
+        assert_eq!(feedback, pattern("YYYYY"));
     }
 
     #[test]
     fn test_parse_input() {
         let word = "CRANE";
         let feedback = "GYYBB";
-        let expected_feedback: Vec<char> = feedback.chars().collect();
+        let expected_feedback = pattern(feedback);
 
         let input = format!("{} {}", word, feedback);
-        let result = parse_input(&input);
+        let result = parse_input(&input, 5);
 
         assert!(result.is_ok());
         let guess = result.unwrap();
@@ -188,85 +665,194 @@ mod tests {
     #[test]
     fn test_parse_input_invalid_word_length() {
         let input = "CRAN GYYBB";
-        let result = parse_input(&input);
+        let result = parse_input(input, 5);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_parse_input_invalid_feedback_length() {
         let input = "CRANE GYBB";
-        let result = parse_input(&input);
+        let result = parse_input(input, 5);
         assert!(result.is_err());
+
+        // Too long to encode: rejected rather than overflowing.
+        let input = format!("CRANE {}", "G".repeat(64));
+        assert!(parse_input(&input, 5).is_err());
+        assert!("GGGGGGGGGGGGG".parse::<Pattern>().is_err());
     }
 
     #[test]
     fn test_parse_input_invalid_feedback_char() {
         let input = "CRANE GYxBx";
-        let result = parse_input(&input);
+        let result = parse_input(input, 5);
         assert!(result.is_err());
     }
 
+    fn matching(words: &[&str], knowledge: &Knowledge) -> Vec<String> {
+        words
+            .iter()
+            .filter(|w| knowledge.matches(w))
+            .map(|w| w.to_string())
+            .collect()
+    }
+
     #[test]
-    fn test_filter_by_green_only() {
-        let mut words = vec![
-            "CRANE".to_string(),
-            "CRATE".to_string(),
-            "BRAKE".to_string(),
-            "DRAKE".to_string(),
-        ];
+    fn test_knowledge_green_only() {
+        let words = ["CRANE", "CLOUD", "CHOMP", "BLOCK", "DITCH"];
+        let guess = Guess::new("CRANE".to_string(), pattern("GBBBB"));
+        let knowledge = Knowledge::from_guesses([&guess]);
 
-        let guess = Guess::new("CRANE".to_string(), vec!['G', 'B', 'B', 'B', 'B']);
+        assert_eq!(knowledge.fixed(0), Some('C'));
+        assert_eq!(matching(&words, &knowledge), vec!["CLOUD", "CHOMP"]);
+    }
 
-        filter_by_green(&mut words, &guess);
+    #[test]
+    fn test_knowledge_yellow_only() {
+        let words = ["CRANE", "BRAKE", "SORRY", "TUMOR", "THINK", "ROBOT"];
+        let guess = Guess::new("CRANE".to_string(), pattern("BYBBB"));
+        let knowledge = Knowledge::from_guesses([&guess]);
 
-        assert!(words.contains(&"CRANE".to_string()));
-        assert!(words.contains(&"CRATE".to_string()));
-        assert!(!words.contains(&"BRAKE".to_string()));
-        assert!(!words.contains(&"DRAKE".to_string()));
+        assert!(!knowledge.allows(1, 'R'));
+        assert_eq!(knowledge.min_count('R'), 1);
+        assert_eq!(
+            matching(&words, &knowledge),
+            vec!["SORRY", "TUMOR", "ROBOT"]
+        );
     }
 
     #[test]
-    fn test_filter_by_yellow_only() {
-        let mut words = vec![
-            "CRANE".to_string(),
-            "BRAKE".to_string(),
-            "RAVEN".to_string(),
-            "SOBER".to_string(),
-            "THINK".to_string(),
-        ];
+    fn test_knowledge_black_only() {
+        let words = ["CRANE", "STAIR", "POUND", "LIGHT", "DUMPS", "GLYPH"];
+        let guess = Guess::new("CRANE".to_string(), pattern("BBBBB"));
+        let knowledge = Knowledge::from_guesses([&guess]);
 
-        let guess = Guess::new("CRANE".to_string(), vec!['B', 'Y', 'B', 'B', 'B']);
+        assert_eq!(knowledge.excluded_letters(), vec!['A', 'C', 'E', 'N', 'R']);
+        assert_eq!(
+            matching(&words, &knowledge),
+            vec!["LIGHT", "DUMPS", "GLYPH"]
+        );
+    }
 
-        filter_by_yellow(&mut words, &guess);
+    #[test]
+    fn test_knowledge_duplicate_gray_caps_count() {
+        // SPEED against SPELT: the second E is gray, so there is exactly one E.
+        let guess = Guess::new("SPEED".to_string(), calculate_feedback("SPEED", "SPELT"));
+        assert_eq!(guess.feedback, pattern("GGGBB"));
+        let knowledge = Knowledge::from_guesses([&guess]);
 
-        assert!(words.contains(&"RAVEN".to_string()));
-        assert!(words.contains(&"SOBER".to_string()));
-        assert!(!words.contains(&"CRANE".to_string()));
-        assert!(!words.contains(&"BRAKE".to_string()));
-        assert!(!words.contains(&"THINK".to_string()));
+        assert_eq!(knowledge.min_count('E'), 1);
+        assert_eq!(knowledge.max_count('E'), Some(1));
+        assert!(knowledge.matches("SPELT"));
+        assert!(knowledge.matches("SPELL"));
+        assert!(!knowledge.matches("SPEER"));
+        assert!(!knowledge.matches("SPEED"));
+
+        let mut state = GameState::new(CandidateSet::full(1), &Alphabet::english());
+        state.add_attempt(guess);
+        assert!(state.available_letters.contains(&'E'));
+        assert!(!state.available_letters.contains(&'D'));
     }
 
     #[test]
-    fn test_filter_by_black_only() {
-        let mut words = vec![
-            "CRANE".to_string(),
-            "STAIR".to_string(),
-            "POUND".to_string(),
-            "LIGHT".to_string(),
-            "DUMPS".to_string(),
-            "GLYPH".to_string(),
-        ];
+    fn test_knowledge_detects_contradiction() {
+        let first = Guess::new("SALET".to_string(), pattern("BBBBY"));
+        let second = Guess::new("CRANE".to_string(), pattern("BBBBY"));
+        let knowledge = Knowledge::from_guesses([&first, &second]);
+        assert!(knowledge.is_contradictory());
+        assert!(!Knowledge::from_guesses([&first]).is_contradictory());
+    }
 
-        let guess = Guess::new("CRANE".to_string(), vec!['B', 'B', 'B', 'B', 'B']);
+    /// Deterministic pseudo-random indices for the property tests.
+    fn lcg(seed: &mut u64, bound: usize) -> usize {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((*seed >> 33) as usize) % bound
+    }
 
-        filter_by_black(&mut words, &guess);
+    /// Every 5-letter word over a tiny alphabet, so duplicates are everywhere.
+    fn duplicate_heavy_words() -> Vec<String> {
+        let letters = ['E', 'S', 'P', 'D'];
+        (0..4usize.pow(5))
+            .map(|mut n| {
+                (0..5)
+                    .map(|_| {
+                        let c = letters[n % 4];
+                        n /= 4;
+                        c
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 
-        assert!(!words.contains(&"CRANE".to_string()));
-        assert!(!words.contains(&"STAIR".to_string()));
-        assert!(!words.contains(&"POUND".to_string()));
-        assert!(words.contains(&"LIGHT".to_string()));
-        assert!(words.contains(&"DUMPS".to_string()));
-        assert!(words.contains(&"GLYPH".to_string()));
+    /// The embedded list, with its pattern table cached in a temp dir rather
+    /// than the user's cache.
+    fn embedded_dict() -> Dictionary {
+        static CACHE_DIR: std::sync::Once = std::sync::Once::new();
+        CACHE_DIR.call_once(|| {
+            let dir = std::env::temp_dir().join("wordle-test-cache");
+            std::env::set_var("WORDLE_CACHE_DIR", dir);
+        });
+        Dictionary::embedded().unwrap()
+    }
+
+    /// Six answers that differ only in the first letter, plus `probes` as extra guesses.
+    fn six_ight_answers(probes: &[&str]) -> Dictionary {
+        let answers = ["FIGHT", "LIGHT", "MIGHT", "NIGHT", "SIGHT", "TIGHT"];
+        Dictionary::with_guesses(
+            answers.iter().map(|w| w.to_string()).collect(),
+            probes.iter().map(|w| w.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn prop_knowledge_matches_exactly_the_feedback_bucket() {
+        let mut seed = 7;
+        for words in [load_words().unwrap(), duplicate_heavy_words()] {
+            for _ in 0..60 {
+                let g = &words[lcg(&mut seed, words.len())];
+                let s = &words[lcg(&mut seed, words.len())];
+                let feedback = calculate_feedback(g, s);
+                let knowledge = Knowledge::from_guesses([&Guess::new(g.clone(), feedback)]);
+
+                assert!(!knowledge.is_contradictory());
+                for w in &words {
+                    assert_eq!(
+                        knowledge.matches(w),
+                        calculate_feedback(g, w) == feedback,
+                        "guess {} answer {} word {}",
+                        g,
+                        s,
+                        w
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prop_knowledge_combines_guesses_exactly() {
+        let mut seed = 11;
+        let words = duplicate_heavy_words();
+        for _ in 0..60 {
+            let s = &words[lcg(&mut seed, words.len())];
+            let guesses: Vec<Guess> = (0..3)
+                .map(|_| {
+                    let g = words[lcg(&mut seed, words.len())].clone();
+                    let feedback = calculate_feedback(&g, s);
+                    Guess::new(g, feedback)
+                })
+                .collect();
+            let knowledge = Knowledge::from_guesses(&guesses);
+
+            for w in &words {
+                let consistent = guesses
+                    .iter()
+                    .all(|g| calculate_feedback(&g.word, w) == g.feedback);
+                assert_eq!(knowledge.matches(w), consistent, "answer {} word {}", s, w);
+            }
+        }
     }
 
     #[test]
@@ -281,7 +867,7 @@ mod tests {
             "CHAIN".to_string(),
         ];
 
-        let guess = Guess::new("CRANE".to_string(), vec!['B', 'G', 'G', 'Y', 'B']);
+        let guess = Guess::new("CRANE".to_string(), pattern("BGGYB"));
 
         filter_words(&mut words, &guess);
 
@@ -293,4 +879,919 @@ mod tests {
         assert!(!words.contains(&"PLAIN".to_string()));
         assert!(!words.contains(&"CHAIN".to_string()));
     }
+
+    #[test]
+    fn test_pattern_parse_styles() {
+        let letters: Pattern = "gybbg".parse().unwrap();
+        let emoji: Pattern = "🟩🟨⬜⬛🟩".parse().unwrap();
+        let trits: Pattern = "21002".parse().unwrap();
+
+        assert_eq!(letters, emoji);
+        assert_eq!(letters, trits);
+        assert_eq!(letters.to_string(), "GYBBG");
+        assert_eq!(format!("{:#}", letters), "🟩🟨⬜⬜🟩");
+        assert_eq!(letters.render(PatternStyle::Trits), "21002");
+        assert!("GYXBG".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_pattern_id_round_trip() {
+        for id in 0..num_patterns(5) {
+            let p = Pattern::from_id(id, 5);
+            assert_eq!(Pattern::from_tiles(p.tiles()), p);
+            assert_eq!(
+                p.render(PatternStyle::Letters).parse::<Pattern>().unwrap(),
+                p
+            );
+        }
+        assert_eq!(pattern("GGGGG").id(), 242);
+        assert!(Pattern::all_green(5).is_all_green());
+        assert_eq!(pattern("BBBBG").tile(4), FeedbackType::Green);
+    }
+
+    #[test]
+    fn test_pattern_table_matches_feedback() {
+        let words: Vec<String> = load_words().unwrap().into_iter().step_by(37).collect();
+        let table = PatternTable::build(&words, &words);
+        for (g, guess) in words.iter().enumerate() {
+            for (s, solution) in words.iter().enumerate() {
+                assert_eq!(table.get(g, s), feedback_id(guess, solution));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pattern_table_cache_round_trip() {
+        let words: Vec<String> = ["CRANE", "SLATE", "LEVEL", "LLAMA"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let table = PatternTable::build(&words, &words);
+        let hash = dictionary_hash(&words, &words);
+        let path = std::env::temp_dir().join(format!("wordle-test-{}.bin", std::process::id()));
+
+        table.save(&path, hash).unwrap();
+        let loaded = PatternTable::load(&path, hash).unwrap();
+        assert!(loaded.row(2).eq(table.row(2)));
+
+        let other: Vec<String> = words.iter().rev().cloned().collect();
+        assert!(PatternTable::load(&path, dictionary_hash(&other, &other)).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_candidate_set_ops() {
+        let mut a = CandidateSet::full(130);
+        assert_eq!(a.len(), 130);
+        assert!(!a.contains(130));
+
+        let b = CandidateSet::from_indices(130, [0, 63, 64, 129]);
+        assert_eq!(a.intersection_len(&b), 4);
+        a.intersect_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![0, 63, 64, 129]);
+
+        a.remove(63);
+        assert_eq!(a.first(), Some(0));
+        assert_eq!(a.len(), 3);
+        a.clear();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_dictionary_filter_matches_feedback() {
+        let words: Vec<String> = load_words().unwrap().into_iter().step_by(11).collect();
+        let dict = Dictionary::new(words.clone());
+        let solution = &words[42];
+
+        for guess in ["SALET", "LEVEL", "ZZZZZ"] {
+            let feedback = calculate_feedback(guess, solution);
+            let mut candidates = CandidateSet::full(dict.num_answers());
+            dict.filter(&mut candidates, &Guess::new(guess.to_string(), feedback));
+
+            let expected: Vec<&str> = words
+                .iter()
+                .filter(|w| calculate_feedback(guess, w) == feedback)
+                .map(|w| w.as_str())
+                .collect();
+            assert_eq!(dict.words_in(&candidates), expected);
+        }
+    }
+
+    #[test]
+    fn test_bucket_sizes_are_popcounts() {
+        let words: Vec<String> = load_words().unwrap().into_iter().step_by(7).collect();
+        let dict = Dictionary::new(words);
+        let candidates =
+            CandidateSet::from_indices(dict.num_answers(), (0..dict.num_answers()).step_by(3));
+
+        let buckets = dict.bucket_sizes(&candidates, 5);
+        assert_eq!(
+            buckets.iter().map(|&(_, n)| n).sum::<usize>(),
+            candidates.len()
+        );
+        for (pid, n) in buckets {
+            let direct = candidates
+                .iter()
+                .filter(|&s| dict.table().get(5, s) == pid)
+                .count();
+            assert_eq!(n, direct);
+        }
+    }
+
+    #[test]
+    fn test_feedback_for_long_words() {
+        assert_eq!(calculate_feedback("PLANET", "PLANES"), pattern("GGGGGB"));
+        assert_eq!(calculate_feedback("LISTEN", "SILENT"), pattern("YGYYYY"));
+        let twelve = calculate_feedback("ABCDEFGHIJKL", "ABCDEFGHIJKL");
+        assert!(twelve.is_all_green());
+        assert_eq!(twelve.len(), MAX_WORD_LEN);
+    }
+
+    #[test]
+    fn test_parse_input_uses_word_length() {
+        let guess = parse_input("planet gyybbg", 6).unwrap();
+        assert_eq!(guess.word, "PLANET");
+        assert_eq!(guess.feedback, pattern("GYYBBG"));
+        assert!(parse_input("CRANE GYYBB", 6).is_err());
+        assert!(parse_input("PLANET GYYBB", 6).is_err());
+    }
+
+    #[test]
+    fn test_wide_pattern_table_and_dictionary() {
+        let words: Vec<String> = ["PLANET", "PLANES", "SILENT", "LISTEN", "TINSEL", "ROCKET"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let dict = Dictionary::new(words.clone());
+        assert_eq!(dict.word_len(), 6);
+
+        for (g, guess) in words.iter().enumerate() {
+            for (s, solution) in words.iter().enumerate() {
+                assert_eq!(dict.table().get(g, s), feedback_id(guess, solution));
+            }
+        }
+
+        let hash = dictionary_hash(&words, &words);
+        let path = std::env::temp_dir().join(format!("wordle-wide-{}.bin", std::process::id()));
+        dict.table().save(&path, hash).unwrap();
+        let loaded = PatternTable::load(&path, hash).unwrap();
+        assert!(loaded.row(3).eq(dict.table().row(3)));
+        assert_eq!(loaded.word_len(), 6);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_bucket_counts_sparse_matches_dense() {
+        let pids = [5usize, 3, 5, 0, 3, 5];
+        let dense = bucket_counts(pids.iter().copied(), 5);
+        let sparse = bucket_counts(pids.iter().copied(), MAX_WORD_LEN);
+        assert_eq!(dense, vec![(0, 1), (3, 2), (5, 3)]);
+        assert_eq!(dense, sparse);
+
+        let all_green = Pattern::all_green(MAX_WORD_LEN).id();
+        let (h, exp, p_win) = score_patterns([all_green, 0].into_iter(), MAX_WORD_LEN);
+        assert_eq!((h, exp, p_win), (1.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_normalize_non_ascii_words() {
+        assert_eq!(normalize_word(" niño "), "NIÑO");
+        // N followed by a combining tilde is the same letter as a precomposed Ñ
+        assert_eq!(normalize_word("nin\u{303}o"), "NIÑO");
+        // ß has no single-letter uppercase, so it stays one tile
+        assert_eq!(normalize_word("straße"), "STRAßE");
+        assert_eq!(word_length("STRAßE"), 6);
+    }
+
+    #[test]
+    fn test_feedback_with_non_ascii_letters() {
+        assert_eq!(calculate_feedback("AÑEJO", "AÑEJO"), pattern("GGGGG"));
+        assert_eq!(calculate_feedback("NIÑOS", "AÑEJO"), pattern("BBYYB"));
+        assert_eq!(calculate_feedback("GRÜßE", "GRÖßE"), pattern("GGBGG"));
+
+        let guess = parse_input("niños gybbg", 5).unwrap();
+        assert_eq!(guess.word, "NIÑOS");
+        assert_eq!(guess.feedback, pattern("GYBBG"));
+    }
+
+    #[test]
+    fn test_non_ascii_dictionary_and_letter_pool() {
+        let words: Vec<String> = ["AÑEJO", "NIÑOS", "ÁRBOL", "CAÑAS", "PERRO"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let dict = Dictionary::new(words.clone());
+        let alphabet = dict.alphabet();
+        assert_eq!(dict.word_len(), 5);
+        assert_eq!(alphabet.index_of('Z'), Some(25));
+        assert!(alphabet.contains('Ñ') && alphabet.contains('Á'));
+        assert_eq!(alphabet.len(), 28);
+
+        let guess = Guess::new("NIÑOS".to_string(), calculate_feedback("NIÑOS", "PERRO"));
+        let mut candidates = CandidateSet::full(dict.num_answers());
+        dict.filter(&mut candidates, &guess);
+        assert_eq!(dict.words_in(&candidates), vec!["PERRO"]);
+
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), alphabet);
+        state.add_attempt(guess);
+        let available = state.get_available_letters_sorted(alphabet);
+        assert!(!available.contains(&'Ñ'));
+        assert!(available.contains(&'O') && available.contains(&'Á'));
+        assert_eq!(available.last(), Some(&'Á'));
+    }
+
+    #[test]
+    fn test_diagnose_reports_conflicting_attempts() {
+        let dict = embedded_dict();
+        let attempts = vec![
+            parse_input("SALET BBBBY", 5).unwrap(),
+            parse_input("STORM BYBBB", 5).unwrap(),
+            parse_input("CRANE BBBBY", 5).unwrap(),
+        ];
+        assert!(candidates_for(&attempts, &dict).is_empty());
+
+        let diagnosis = diagnose(&attempts, &dict);
+        // SALET and CRANE disagree about whether E is in the word; STORM is innocent.
+        assert_eq!(diagnosis.conflicts.len(), 1);
+        assert_eq!(diagnosis.conflicts[0].attempts, vec![0, 2]);
+        assert!(diagnosis.conflicts[0].impossible);
+    }
+
+    #[test]
+    fn test_diagnose_handles_many_attempts() {
+        let dict = embedded_dict();
+        // Too many rows to check every subset: one minimal conflict is found.
+        let mut attempts = vec![parse_input("SALET BBBBY", 5).unwrap()];
+        attempts.extend((0..40).map(|_| parse_input("STORM BYBBB", 5).unwrap()));
+        attempts.push(parse_input("CRANE BBBBY", 5).unwrap());
+
+        let diagnosis = diagnose(&attempts, &dict);
+        assert_eq!(diagnosis.conflicts.len(), 1);
+        assert_eq!(diagnosis.conflicts[0].attempts, vec![0, 41]);
+    }
+
+    #[test]
+    fn test_diagnose_finds_mistyped_tile() {
+        let dict = embedded_dict();
+        // The answer is CIDER; the second row has its E yellow mistyped as gray.
+        let mut attempts: Vec<Guess> = ["SALET", "BRINE"]
+            .iter()
+            .map(|w| Guess::new(w.to_string(), calculate_feedback(w, "CIDER")))
+            .collect();
+        let typo = TileEdit {
+            attempt: 1,
+            position: 4,
+            from: FeedbackType::Yellow,
+            to: FeedbackType::Black,
+        };
+        assert_eq!(attempts[1].feedback.tile(4), typo.from);
+        attempts[1].feedback = pattern("BYYBB");
+        assert!(candidates_for(&attempts, &dict).is_empty());
+
+        let diagnosis = diagnose(&attempts, &dict);
+        assert!(diagnosis.fixes.iter().all(|fix| fix.edits.len() == 1));
+        let fix = diagnosis
+            .fixes
+            .iter()
+            .find(|fix| fix.edits[0].attempt == 1 && fix.edits[0].position == 4)
+            .expect("restoring the yellow E should be offered");
+        assert_eq!(fix.edits[0].to, FeedbackType::Yellow);
+        assert!(fix.candidates.contains(dict.index_of("CIDER").unwrap()));
+        assert_eq!(fix.candidates, candidates_for(&fix.attempts, &dict));
+    }
+
+    #[test]
+    fn test_parse_grid_styles() {
+        let rows = parse_grid("⬜🟨⬜⬜🟩 / 🟩🟩⬛🟩🟩\nGGGGG\n", 5).unwrap();
+        assert_eq!(
+            rows,
+            vec![pattern("BYBBG"), pattern("GGBGG"), pattern("GGGGG")]
+        );
+        assert!(parse_grid("🟩🟩🟩🟩", 5).is_err());
+        assert!(parse_grid(" / ", 5).is_err());
+    }
+
+    #[test]
+    fn test_reverse_solver_reproduces_grid() {
+        let dict = embedded_dict();
+        let played = ["SALET", "TIMER", "CIDER"];
+        let grid: Vec<String> = played
+            .iter()
+            .map(|w| calculate_feedback(w, "CIDER").to_emoji())
+            .collect();
+        let rows = parse_grid(&grid.join(" / "), 5).unwrap();
+        let answer = answer_index("cider", &dict).unwrap();
+
+        for (row, word) in rows.iter().zip(played) {
+            let words = row_words(*row, answer, &dict);
+            assert!(words.contains(&dict.index_of(word).unwrap()));
+            assert!(words
+                .iter()
+                .all(|&g| calculate_feedback(dict.word(g), "CIDER") == *row));
+        }
+        assert_eq!(row_words(rows[2], answer, &dict), vec![answer]);
+
+        let sequences = rank_sequences(&rows, answer, &dict, 5);
+        assert_eq!(sequences.len(), 5);
+        assert!(sequences[0].hard_mode);
+        assert!(sequences.windows(2).all(|w| {
+            (w[0].hard_mode && !w[1].hard_mode)
+                || (w[0].hard_mode == w[1].hard_mode && w[0].bits >= w[1].bits)
+        }));
+        for sequence in &sequences {
+            let replayed: Vec<Pattern> = sequence
+                .words
+                .iter()
+                .map(|w| calculate_feedback(w, "CIDER"))
+                .collect();
+            assert_eq!(replayed, rows);
+        }
+    }
+
+    #[test]
+    fn test_knowledge_keeps_hints() {
+        let knowledge = Knowledge::from_guesses([&parse_input("SALET BYBBG", 5).unwrap()]);
+        assert!(knowledge.keeps_hints("TRAIT"));
+        assert!(knowledge.keeps_hints("BLAST"));
+        assert!(!knowledge.keeps_hints("TRAIN")); // T must stay last
+        assert!(!knowledge.keeps_hints("ROUST")); // A must be used
+    }
+
+    #[test]
+    fn test_parse_share_text() {
+        let text = "Wordle 1,234 4/6*\n\n⬜🟨⬜⬜⬜\n⬜🟨🟨⬜🟨\n⬜🟩🟩🟩🟩\n🟩🟩🟩🟩🟩\n";
+        assert!(is_share_text(text));
+        let record = parse_share(text).unwrap();
+        assert_eq!(record.game, "Wordle");
+        assert_eq!(record.puzzle, 1234);
+        assert!(record.solved && record.hard_mode);
+        assert_eq!((record.attempts(), record.max_attempts), (4, 6));
+        assert_eq!(record.rows[1], pattern("BYYBY"));
+
+        // Dark mode, variation selectors, a failed game and a trailing link.
+        let failed = "Wordle 987 X/6\n⬛⬛⬛⬛⬛\n⬛️⬛️🟨⬛️⬛️\n⬛🟩⬛⬛⬛\n🟩🟩⬛⬛⬛\n🟩🟩🟩⬛🟩\n🟩🟩🟩⬛🟩\nhttps://www.nytimes.com/games/wordle";
+        let record = parse_share(failed).unwrap();
+        assert_eq!(record.puzzle, 987);
+        assert!(!record.solved && !record.hard_mode);
+        assert_eq!(record.rows.len(), 6);
+        assert_eq!(record.rows[1], pattern("BBYBB"));
+
+        // High-contrast tiles and a European thousands separator.
+        let record = parse_share("Wordle 1.001 2/6\n🟦⬜⬜🟧⬜\n🟧🟧🟧🟧🟧").unwrap();
+        assert_eq!(record.puzzle, 1001);
+        assert_eq!(record.rows[0], pattern("YBBGB"));
+
+        assert!(parse_share("Wordle 1,234 3/6\n⬜⬜⬜⬜⬜\n🟩🟩🟩🟩🟩").is_err());
+        assert!(parse_share("Wordle 1,234 X/6\n🟩🟩🟩🟩🟩").is_err());
+        assert!(!is_share_text("CRANE BYYGG"));
+    }
+
+    #[test]
+    fn test_analyze_share() {
+        let dict = embedded_dict();
+        let record = parse_share("Wordle 1,234 2/6*\n⬜🟨🟩⬜🟩\n🟩🟩🟩🟩🟩").unwrap();
+        let rows = analyze_share(&record, &["crane", "stare"], &dict).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].guess,
+            Guess::new("CRANE".to_string(), pattern("BYGBG"))
+        );
+        assert_eq!((rows[0].before, rows[1].after), (dict.num_answers(), 1));
+        assert_eq!(rows[1].before, rows[0].after);
+        let bits = (rows[0].before as f64 / rows[0].after as f64).log2();
+        assert!((rows[0].actual_bits - bits).abs() < 1e-9);
+        assert!(rows[0].expected_bits > 5.0);
+
+        assert!(analyze_share(&record, &["crane"], &dict).is_err());
+        // STARE would not give CRANE that first row, so nothing fits the second.
+        assert!(analyze_share(&record, &["stare", "crane"], &dict).is_err());
+    }
+
+    #[test]
+    fn test_share_record_feeds_guess_pipeline() {
+        let dict = embedded_dict();
+        let text = "Wordle 1,234 3/6\n\n⬜⬜⬜🟩⬜\n⬜🟩⬜🟩🟩\n🟩🟩🟩🟩🟩";
+        let record = parse_share(text).unwrap();
+        assert!(record.guesses(&["salet", "cider"]).is_err());
+
+        let guesses = record.guesses(&["salet", "miner", "cider"]).unwrap();
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        for guess in &guesses[..2] {
+            state.add_attempt(guess.clone());
+            dict.filter(&mut state.candidates, guess);
+        }
+        assert!(state.candidates.contains(dict.index_of("CIDER").unwrap()));
+        assert!(guesses[2].feedback.is_all_green());
+    }
+
+    #[test]
+    fn test_hard_mode_messages() {
+        let mut state = GameState::new(CandidateSet::full(1), &Alphabet::english());
+        state.add_attempt(parse_input("CRANE BGBBY", 5).unwrap());
+        assert_eq!(state.hard_mode_violation("SLOTH"), None); // not in hard mode
+
+        state.hard_mode = true;
+        assert_eq!(
+            state.hard_mode_violation("SLOTH"),
+            Some("2nd letter must be R".to_string())
+        );
+        assert_eq!(
+            state.hard_mode_violation("FROST"),
+            Some("Guess must contain E".to_string())
+        );
+        assert!(state.allows_guess("TREND"));
+
+        state.add_attempt(parse_input("EERIE YYBBB", 5).unwrap());
+        assert_eq!(
+            state.hard_mode_violation("TREND"),
+            Some("Guess must contain E twice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hard_mode_suggestions_keep_hints() {
+        let dict = embedded_dict();
+        let games = [("SALET", "CIDER"), ("CRANE", "BOOTH"), ("SLATE", "PIPER")];
+        for (opener, answer) in games {
+            let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+            state.hard_mode = true;
+            let guess = Guess::new(opener.to_string(), calculate_feedback(opener, answer));
+            state.add_attempt(guess.clone());
+            dict.filter(&mut state.candidates, &guess);
+
+            for solver in [create_solver("entropy"), create_solver("simple")] {
+                let suggestion = solver.suggest_guess(&state, &dict);
+                assert_eq!(
+                    state.hard_mode_violation(&suggestion),
+                    None,
+                    "{}",
+                    suggestion
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_separate_answer_and_guess_lists() {
+        // FLMNS is no answer, but tells all six apart.
+        let dict = six_ight_answers(&["FLMNS", "LIGHT", "CROWD"]);
+        let answers = ["FIGHT", "LIGHT", "MIGHT", "NIGHT", "SIGHT", "TIGHT"];
+        assert_eq!(dict.num_answers(), 6);
+        assert_eq!(dict.num_guesses(), 8); // LIGHT is not repeated
+        assert_eq!(dict.answers(), answers);
+        assert_eq!(dict.answer_index("TIGHT"), Some(5));
+        assert_eq!(dict.answer_index("FLMNS"), None);
+        let probe = dict.index_of("FLMNS").unwrap();
+        assert!(probe >= dict.num_answers());
+        assert_eq!(dict.table().num_solutions(), 6);
+
+        // Candidates only ever hold answers.
+        let guess = parse_input("CROWD BBBBB", 5).unwrap();
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        dict.filter(&mut state.candidates, &guess);
+        state.add_attempt(guess);
+        assert_eq!(dict.words_in(&state.candidates), answers);
+
+        assert_eq!(
+            EntropyMaximizer::default().suggest_guess(&state, &dict),
+            "FLMNS"
+        );
+        let (entropy, _, _) = score_guess(probe, &[0, 1, 2, 3, 4, 5], &dict);
+        assert!((entropy - 6f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_opening_book() {
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(5).collect();
+        let analyzer = StartingWordAnalyzer::from_words(words);
+        let book = OpeningBook::build(&analyzer);
+        let opener_alone = analyzer.expected_remaining_after(&book.opener, &[]);
+        let dict = analyzer.into_dictionary();
+        assert!(dict.index_of(&book.opener).is_some());
+
+        // Without replies, what remains is what the opener alone leaves, on the
+        // scale the solvers score guesses with.
+        let start = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        let scored = Suggestion::score(&book.opener, &start, &dict);
+        assert!((opener_alone - scored.expected_remaining).abs() < 1e-9);
+
+        // A reply for every feedback the opener can get, and the text form round-trips.
+        let opener = dict.index_of(&book.opener).unwrap();
+        for (pid, _) in dict.bucket_sizes(&CandidateSet::full(dict.num_answers()), opener) {
+            let feedback = Pattern::from_id(pid, 5);
+            assert_eq!(book.reply(feedback).is_some(), !feedback.is_all_green());
+        }
+        assert_eq!(OpeningBook::parse(&book.to_text()).unwrap(), book);
+        assert!(OpeningBook::parse("BBBBB CRANE").is_err());
+
+        // Every strategy opens from the book and follows it for one reply.
+        let dict = dict.with_book(book.clone());
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        for name in ["simple", "entropy", "minimax", "frequency", "mcts"] {
+            assert_eq!(
+                create_solver(name).suggest_guess(&state, &dict),
+                book.opener
+            );
+        }
+        let answer = dict.word(dict.num_answers() - 1).to_string();
+        let guess = Guess::new(
+            book.opener.clone(),
+            calculate_feedback(&book.opener, &answer),
+        );
+        dict.filter(&mut state.candidates, &guess);
+        state.add_attempt(guess.clone());
+        if !guess.feedback.is_all_green() {
+            let reply = book.reply(guess.feedback).unwrap();
+            assert_eq!(create_solver("entropy").suggest_guess(&state, &dict), reply);
+        }
+    }
+
+    #[test]
+    fn test_ranked_suggestions() {
+        let dict = six_ight_answers(&["CROWD", "FLMNS", "PLUMB"]);
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        state.add_attempt(parse_input("CROWD BBBBB", 5).unwrap());
+
+        let ranked = EntropyMaximizer::new(ProbePolicy::full()).suggest(&state, &dict, 3);
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0].word, "FLMNS");
+        assert!((ranked[0].entropy - 6f64.log2()).abs() < 1e-9);
+        assert!((ranked[0].expected_remaining - 1.0).abs() < 1e-9);
+        assert_eq!(ranked[0].p_win, 0.0);
+        assert!(!ranked[0].is_candidate);
+        assert!(ranked.windows(2).all(|w| w[0].entropy >= w[1].entropy));
+        assert_eq!(ranked[1].word, "PLUMB");
+
+        // Every strategy ranks its own pick first, with the metrics scored afresh.
+        for name in ["simple", "entropy", "minimax", "frequency", "mcts"] {
+            let solver = create_solver(name);
+            let ranked = solver.suggest(&state, &dict, 4);
+            assert!(!ranked.is_empty() && ranked.len() <= 4, "{}", name);
+            assert_eq!(
+                ranked[0].word,
+                solver.suggest_guess(&state, &dict),
+                "{}",
+                name
+            );
+            for s in &ranked {
+                assert_eq!(*s, Suggestion::score(&s.word, &state, &dict), "{}", name);
+            }
+            assert!(solver.suggest(&state, &dict, 0).is_empty(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_explain_guess() {
+        let dict = six_ight_answers(&["CROWD", "PLUMB"]);
+        let state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+
+        let why = explain("PLUMB", &state, &dict, 2);
+        assert_eq!(why.worst_case, 4);
+        assert_eq!(why.suggestion, Suggestion::score("PLUMB", &state, &dict));
+        let sizes: Vec<usize> = why.buckets.iter().map(|b| b.size).collect();
+        assert_eq!(sizes, vec![4, 1, 1]);
+        assert_eq!(why.buckets[0].pattern, pattern("BBBBB"));
+        assert_eq!(why.buckets[0].examples, vec!["FIGHT", "NIGHT"]);
+        let mut singles: Vec<&str> = why.buckets[1..]
+            .iter()
+            .map(|b| b.examples[0].as_str())
+            .collect();
+        singles.sort();
+        assert_eq!(singles, vec!["LIGHT", "MIGHT"]);
+
+        // A word outside the lists is explained from its feedback directly.
+        let why = explain("LUMPS", &state, &dict, 3);
+        assert_eq!(why.buckets.iter().map(|b| b.size).sum::<usize>(), 6);
+        assert_eq!(why.worst_case, 3);
+
+        // Every candidate splits the rest the same way; the first one wins.
+        let best = best_candidate(&state, &dict).unwrap();
+        assert_eq!(best.word, "FIGHT");
+        assert!(best.is_candidate);
+    }
+
+    #[test]
+    fn test_probe_sampling_policies() {
+        let dict = six_ight_answers(&["CROWD", "ABOUT", "FLMNS", "GHOST", "PLUMB"]);
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        state.add_attempt(parse_input("CROWD BBBBB", 5).unwrap());
+
+        // Six candidates: the endgame solver picks its probes by the policy too.
+        let suggest =
+            |policy: ProbePolicy| EntropyMaximizer::new(policy).suggest_guess(&state, &dict);
+        assert_eq!(suggest(ProbePolicy::full()), "FLMNS");
+        // The one probe kept by coverage is the one whose letters split the answers.
+        assert_eq!(suggest(ProbePolicy::coverage().with_budget(1)), "FLMNS");
+        assert_ne!(suggest(ProbePolicy::coverage().with_budget(0)), "FLMNS");
+
+        // A seeded sample always scores the same probes.
+        for seed in 0..8 {
+            let policy = ProbePolicy::random(seed).with_budget(1);
+            assert_eq!(suggest(policy), suggest(policy));
+        }
+        let found = (0..32)
+            .filter(|&seed| suggest(ProbePolicy::random(seed).with_budget(1)) == "FLMNS")
+            .count();
+        assert!(found > 0 && found < 32);
+    }
+
+    #[test]
+    fn test_frequency_prior_weights_scores() {
+        let words: Vec<String> = ["CRANE", "CRATE", "GRAPE", "DRAPE"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let path = std::env::temp_dir().join(format!("wordle-freq-{}.txt", std::process::id()));
+        std::fs::write(&path, "# word,count\ncrane,900\ncrate 50\ngrape\t10\n").unwrap();
+        let counts = load_frequencies(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(counts.get("CRANE"), Some(&900.0));
+
+        let prior = Prior::from_counts(&words, &counts);
+        assert!(!prior.is_uniform());
+        // More frequent ranks higher; DRAPE is missing and ranks last.
+        assert!(prior.weight(0) > prior.weight(1));
+        assert!(prior.weight(1) > prior.weight(2));
+        assert!(prior.weight(2) > prior.weight(3));
+        let p = prior.probabilities(&[0, 1, 2, 3]);
+        assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let uniform = Dictionary::new(words.clone());
+        let weighted = Dictionary::new(words).with_prior(prior.clone());
+        let all = [0, 1, 2, 3];
+        // Uniform weights reproduce the unweighted scores.
+        let (h, exp, pwin) = score_guess(0, &all, &uniform);
+        let (wh, wexp, wpwin) =
+            score_weighted(all.iter().map(|&s| (uniform.table().get(0, s), 1.0)), 5);
+        assert!((h - wh).abs() < 1e-9 && (exp - wexp).abs() < 1e-9 && (pwin - wpwin).abs() < 1e-9);
+
+        // Only CRANE itself comes back all green, so P(win) is its probability.
+        let (h, _, pwin) = score_guess(0, &all, &weighted);
+        assert!((pwin - p[0]).abs() < 1e-9);
+        assert!(h < 2.0); // skewed odds carry less than log2(4) bits
+    }
+
+    #[test]
+    fn test_frequency_analyzer_scores_candidates() {
+        let words: Vec<String> = ["SASSY", "SLATE", "BRAKE", "CRATE", "GRATE", "IRATE"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let freqs = letter_frequencies(words.iter().map(String::as_str));
+        assert!((freqs[&('A', 2)] - 5.0 / 6.0).abs() < 1e-9);
+        let presence = letter_presence(words.iter().map(String::as_str));
+        assert!((presence[&'S'] - 2.0 / 6.0).abs() < 1e-9);
+
+        let dict = Dictionary::new(words);
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        state.attempt_count = 1;
+        let suggestion = FrequencyAnalyzer::new().suggest_guess(&state, &dict);
+        assert!(
+            ["CRATE", "GRATE"].contains(&suggestion.as_str()),
+            "{}",
+            suggestion
+        );
+    }
+
+    #[test]
+    fn test_frequency_analyzer_probes() {
+        let dict = six_ight_answers(&["FLMNS"]);
+        let state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+
+        let plain = FrequencyAnalyzer::new().suggest_guess(&state, &dict);
+        assert_ne!(plain, "FLMNS"); // only candidates without probes
+        assert_eq!(
+            FrequencyAnalyzer::with_probes().suggest_guess(&state, &dict),
+            "FLMNS"
+        );
+    }
+
+    #[test]
+    fn test_minimax_minimizes_largest_bucket() {
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(7).collect();
+        let dict = Dictionary::new(words);
+        let state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        let cands: Vec<usize> = state.candidates.iter().collect();
+
+        let largest = |g: usize| {
+            bucket_counts(cands.iter().map(|&s| dict.table().get(g, s)), 5)
+                .iter()
+                .map(|&(_, c)| c)
+                .max()
+                .unwrap()
+        };
+        let best = (0..dict.num_guesses()).map(largest).min().unwrap();
+        let suggestion = create_solver("minimax").suggest_guess(&state, &dict);
+        assert_eq!(largest(dict.index_of(&suggestion).unwrap()), best);
+    }
+
+    #[test]
+    fn test_lookahead_solves_every_answer() {
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(7).collect();
+        let dict = Dictionary::new(words);
+        let answers: Vec<usize> = (0..dict.num_answers()).collect();
+
+        let lookahead = LookaheadSolver::new(5, 2);
+        let result = benchmark::benchmark(&lookahead, &dict, &answers, false, false);
+        assert!(result.failures.is_empty());
+        assert_eq!(
+            result.solved_within(benchmark::MAX_TURNS),
+            dict.num_answers()
+        );
+
+        // One answer left takes exactly the guess that names it.
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        state.add_attempt(Guess::new("SALET".to_string(), pattern("BBBBB")));
+        state.candidates = CandidateSet::from_indices(dict.num_answers(), [3]);
+        assert_eq!(lookahead.expected_guesses(&state, &dict), 1.0);
+        assert_eq!(lookahead.suggest_guess(&state, &dict), dict.word(3));
+    }
+
+    #[test]
+    fn test_decision_tree_is_optimal_and_round_trips() {
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(3).collect();
+        let dict = Dictionary::new(words);
+        let opener = dict.word(0).to_string();
+
+        let exact = TreeSearch::new(&dict, MAX_DEPTH, None)
+            .build(&opener)
+            .unwrap();
+        let greedy = TreeSearch::new(&dict, MAX_DEPTH, Some(1))
+            .build(&opener)
+            .unwrap();
+        assert_eq!(exact.answers, dict.num_answers());
+        assert!(exact.total_guesses <= greedy.total_guesses);
+
+        let loaded = DecisionTree::from_json(&exact.to_json()).unwrap();
+        assert_eq!(loaded, exact);
+
+        // Playing the tree costs exactly what the search promised.
+        let answers: Vec<usize> = (0..dict.num_answers()).collect();
+        let solver = TreeSolver {
+            tree: loaded,
+            probes: ProbePolicy::default(),
+        };
+        let result = benchmark::benchmark(&solver, &dict, &answers, false, false);
+        assert!(result.failures.is_empty());
+        assert_eq!(result.total_guesses, exact.total_guesses);
+        assert!(result.max_guesses() <= MAX_DEPTH);
+
+        assert!(DecisionTree::from_json("{\"word_len\": 5}").is_err());
+    }
+
+    #[test]
+    fn test_endgame_is_exact() {
+        // Three answers that differ in one letter: guessing one of them may
+        // take three guesses, a probe splitting all three always takes two.
+        let answers: Vec<String> = ["FIGHT", "LIGHT", "MIGHT"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let guesses: Vec<String> = ["FLMNS"].iter().map(|w| w.to_string()).collect();
+        let dict = Dictionary::with_guesses(answers, guesses);
+        let state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        let plan = Endgame::new(&dict, false).solve(&state.candidates).unwrap();
+        assert_eq!(dict.word(plan.guess), "FLMNS");
+        assert_eq!(plan.max_guesses, 2);
+        assert!((plan.expected_guesses - 2.0).abs() < 1e-9);
+        assert_eq!(
+            EntropyMaximizer::default().suggest_guess(&state, &dict),
+            "FLMNS"
+        );
+        // Hard mode keeps to the candidates.
+        let plan = Endgame::new(&dict, true).solve(&state.candidates).unwrap();
+        assert!(plan.guess < dict.num_answers());
+        assert_eq!(plan.max_guesses, 3);
+
+        // With a uniform prior, the expected guesses after each opener feedback
+        // add up to the optimal decision tree's total.
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(7).collect();
+        let dict = Dictionary::new(words);
+        let tree = TreeSearch::new(&dict, MAX_DEPTH, None)
+            .build(dict.word(0))
+            .unwrap();
+        let mut endgame = Endgame::new(&dict, false);
+        let mut total = dict.num_answers() as f64;
+        for (pid, bucket) in dict.masks(0) {
+            if Pattern::from_id(*pid, 5).is_all_green() {
+                continue;
+            }
+            let plan = endgame.solve(bucket).unwrap();
+            total += plan.expected_guesses * bucket.len() as f64;
+        }
+        assert!((total - tree.total_guesses as f64).abs() < 1e-6);
+
+        let ranked = endgame.rank(&CandidateSet::full(dict.num_answers()), 3);
+        assert!(ranked
+            .windows(2)
+            .all(|w| { w[0].expected_guesses <= w[1].expected_guesses + 1e-9 }));
+    }
+
+    #[test]
+    fn test_survival_plays_for_the_turns_left() {
+        let dict = six_ight_answers(&["CROWD", "FLMNS"]);
+        let solver = SurvivalSolver::default();
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        for _ in 0..4 {
+            state.add_attempt(parse_input("CROWD BBBBB", 5).unwrap());
+        }
+
+        // Two turns left: only the probe that splits every answer is safe.
+        assert_eq!(solver.turns_left(&state), 2);
+        assert_eq!(solver.suggest_guess(&state, &dict), "FLMNS");
+        let p = solver.solve_probability(&state, &dict, "FLMNS").unwrap();
+        assert!((p - 1.0).abs() < 1e-9);
+        // A candidate wins now or leaves five to pick from.
+        let p = solver.solve_probability(&state, &dict, "FIGHT").unwrap();
+        assert!((p - 1.0 / 3.0).abs() < 1e-9);
+
+        // One turn left: a coin toss among the candidates.
+        state.add_attempt(parse_input("CROWD BBBBB", 5).unwrap());
+        let guess = solver.suggest_guess(&state, &dict);
+        assert!(dict.answer_index(&guess).is_some());
+        let p = solver.solve_probability(&state, &dict, &guess).unwrap();
+        assert!((p - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(solver.solve_probability(&state, &dict, "FLMNS"), Some(0.0));
+        assert_eq!(
+            EntropyMaximizer::default().solve_probability(&state, &dict, &guess),
+            None
+        );
+    }
+
+    #[test]
+    fn test_adversarial_host_dodges_guesses() {
+        let dict = six_ight_answers(&["FLMNS"]);
+
+        for tie_break in [TieBreak::WorstCase, TieBreak::ExpectedCase] {
+            let mut host = AdversarialHost::new(&dict, tie_break);
+            // The five other answers share one feedback, so FIGHT is never it.
+            assert_eq!(host.respond("FIGHT"), pattern("BGGGG"));
+            assert_eq!(host.candidates().len(), 5);
+            assert_eq!(host.forced_answer(), None);
+
+            // Every bucket holds one word: the host reveals the fewest hints.
+            assert_eq!(host.respond("FLMNS"), pattern("BBBBB"));
+            assert_eq!(host.forced_answer(), Some("TIGHT"));
+            assert_eq!(host.respond("TIGHT"), pattern("GGGGG"));
+        }
+
+        // Words outside the lists get feedback too.
+        let all = CandidateSet::full(dict.num_answers());
+        let feedback = choose_feedback(&dict, &all, "LUMPS", TieBreak::WorstCase);
+        assert_eq!(feedback, pattern("BBBBB"));
+        assert_eq!(TieBreak::parse("expected"), Some(TieBreak::ExpectedCase));
+        assert_eq!(TieBreak::parse("best"), None);
+    }
+
+    #[test]
+    fn test_absurdle_search_is_minimal() {
+        let dict = six_ight_answers(&["FLMNS"]);
+        let all = CandidateSet::full(dict.num_answers());
+        let probe = dict.index_of("FLMNS").unwrap();
+
+        // The probe tells every answer apart, so one more guess finishes.
+        let mut search = AbsurdleSearch::new(&dict, false);
+        assert_eq!(search.solve(&all), Some((2, probe)));
+        let line = search.play_out(&mut AdversarialHost::new(&dict, TieBreak::WorstCase));
+        let words: Vec<&str> = line.iter().map(|t| t.guess.as_str()).collect();
+        assert_eq!(words, ["FLMNS", "TIGHT"]);
+        assert_eq!(line[0].left, 1);
+        assert!(line[1].feedback.is_all_green());
+
+        // Without the probe each guess rules out one answer at most.
+        let mut hard = AbsurdleSearch::new(&dict, true);
+        assert_eq!(hard.solve(&all).map(|(turns, _)| turns), Some(6));
+        assert_eq!(search.solve(&CandidateSet::empty(dict.num_answers())), None);
+
+        let mut state = GameState::new(all, dict.alphabet());
+        assert_eq!(AbsurdleSolver.suggest_guess(&state, &dict), "FLMNS");
+        state.hard_mode = true;
+        let ranked = AbsurdleSolver.suggest(&state, &dict, 3);
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.iter().all(|s| s.is_candidate));
+    }
+
+    #[test]
+    fn test_mcts_is_reproducible_and_solves() {
+        let words: Vec<String> = duplicate_heavy_words().into_iter().step_by(7).collect();
+        let dict = Dictionary::new(words);
+        let mcts = MctsSolver::new(200).with_seed(42);
+
+        let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+        let first = Guess::new(dict.word(0).to_string(), pattern("BBBBB"));
+        dict.filter(&mut state.candidates, &first);
+        state.add_attempt(first);
+        let guess = mcts.suggest_guess(&state, &dict);
+        assert!(dict.index_of(&guess).is_some());
+        assert_eq!(
+            MctsSolver::new(200)
+                .with_seed(42)
+                .suggest_guess(&state, &dict),
+            guess
+        );
+
+        let answers: Vec<usize> = (0..dict.num_answers()).collect();
+        let result = benchmark::benchmark(&mcts, &dict, &answers, false, false);
+        assert!(result.failures.is_empty());
+    }
 }
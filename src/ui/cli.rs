@@ -1,21 +1,42 @@
-use crate::wordle::{core, solver::SolverStrategy, types::*};
+use crate::wordle::{
+    adversary::{AdversarialHost, TieBreak},
+    alphabet::{normalize_word, word_length},
+    analyzer::{self, pattern_to_string, RowAnalysis},
+    candidates::CandidateSet,
+    core,
+    diagnose::{self, FeedbackFix},
+    dictionary::Dictionary,
+    endgame::{Endgame, ENDGAME_MAX},
+    explain,
+    pattern::Pattern,
+    reverse,
+    share::{self, ShareRecord},
+    solver::{SolverStrategy, Suggestion},
+    types::*,
+};
 use std::io::{self, Write};
 
-pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
+pub fn run_solver_mode(solver: Box<dyn SolverStrategy>, dict: &Dictionary, hard_mode: bool) {
     println!("🔍 Wordle Solver Mode");
     println!("====================");
     println!("I'll help you solve today's Wordle!");
     println!("Enter your guesses and feedback (e.g., 'CRANE BYYBB')\n");
 
-    // Load embedded word list
-    let all_words = core::load_words().expect("Failed to load embedded word list");
-    println!("✅ Loaded {} words\n", all_words.len());
+    print_loaded(dict);
 
-    let mut state = GameState::new(all_words.clone());
+    if hard_mode {
+        println!("🔒 Hard mode: suggestions reuse every revealed hint\n");
+    }
+
+    let mut state = new_state(dict, hard_mode);
 
     println!("📝 Starting candidates: {}", state.candidates.len());
-    let suggestion = solver.suggest_guess(&state, &all_words);
-    println!("\n💡 Suggested first guess: {}\n", suggestion);
+    println!();
+    let mut suggestions = print_suggestions(solver.as_ref(), &state, dict, "first");
+    println!();
+
+    // Corrections offered by the last contradiction diagnosis
+    let mut pending_fixes: Vec<FeedbackFix> = Vec::new();
 
     // Solver loop
     loop {
@@ -26,12 +47,43 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
-        io::stdin()
+        let read = io::stdin()
             .read_line(&mut input)
             .expect("Failed to read input");
+        if read == 0 {
+            break; // end of input
+        }
         let input = input.trim();
 
         // Handle commands
+        if let Some(arg) = input.to_lowercase().strip_prefix("/fix") {
+            let fix = arg
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|n| pending_fixes.get(n));
+            match fix {
+                Some(fix) => {
+                    state = replay(&fix.attempts, dict, hard_mode);
+                    pending_fixes.clear();
+                    println!("🔧 Feedback corrected:");
+                    for (i, attempt) in state.attempts.iter().enumerate() {
+                        println!("  {}. {} → {:#}", i + 1, attempt.word, attempt.feedback);
+                    }
+                    println!("\n📝 Candidates remaining: {}", state.candidates.len());
+                    suggestions = print_suggestions(solver.as_ref(), &state, dict, "next");
+                    println!();
+                }
+                None if pending_fixes.is_empty() => {
+                    println!("❌ No fixes to apply\n");
+                }
+                None => {
+                    println!("❌ Pick a fix between 1 and {}\n", pending_fixes.len());
+                }
+            }
+            continue;
+        }
 
         match input.to_lowercase().as_str() {
             "/q" | "/quit" => {
@@ -40,10 +92,16 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
             }
             "/r" | "/reset" => {
                 println!("🔄 Restarting solver...");
-                state = GameState::new(all_words.clone());
+                state = new_state(dict, hard_mode);
+                pending_fixes.clear();
                 println!("📝 Candidates reset to: {}", state.candidates.len());
-                let suggestion = solver.suggest_guess(&state, &all_words);
-                println!("\n💡 Suggested first guess: {}\n", suggestion);
+                println!();
+                suggestions = print_suggestions(solver.as_ref(), &state, dict, "first");
+                println!();
+                continue;
+            }
+            "/why" => {
+                print_why(&suggestions, &state, dict);
                 continue;
             }
             "/h" | "/help" => {
@@ -51,10 +109,14 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
                 println!("  /h, /help   - Show this help message");
                 println!("  /s, /stats  - Show current game statistics");
                 println!("  /r, /reset  - Start over with a fresh word list");
+                println!("  /why        - Explain the last suggestion");
+                println!("  /fix N      - Apply fix N after a contradiction");
                 println!("  /q, /quit   - Exit the solver");
                 println!("\n📝 Input format: WORD FEEDBACK");
                 println!("  Example: CRANE BYYGG");
-                println!("  G=Green(🟩), Y=Yellow(🟨), B=Black(⬜)\n");
+                println!("  G=Green(🟩), Y=Yellow(🟨), B=Black(⬜)");
+                println!("  Or paste a share block (\"Wordle 1,234 4/6\" and its rows),");
+                println!("  then the words you played\n");
                 continue;
             }
             "/s" | "/stats" => {
@@ -63,10 +125,10 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
                 println!("  Candidates:  {} words remaining", state.candidates.len());
                 println!(
                     "  Reduction:   {:.1}% eliminated",
-                    (1.0 - state.candidates.len() as f64 / all_words.len() as f64) * 100.0
+                    (1.0 - state.candidates.len() as f64 / dict.num_answers() as f64) * 100.0
                 );
 
-                let available = state.get_available_letters_sorted();
+                let available = state.get_available_letters_sorted(dict.alphabet());
                 if !available.is_empty() {
                     println!("\n🔤 Available Letters ({}):", available.len());
                     print!("  ");
@@ -83,24 +145,13 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
                 if !state.attempts.is_empty() {
                     println!("\n📝 Previous guesses:");
                     for (i, attempt) in state.attempts.iter().enumerate() {
-                        print!("  {}. {} → ", i + 1, attempt.word);
-                        for &fb in attempt.feedback.iter() {
-                            print!(
-                                "{}",
-                                match fb {
-                                    'G' => "🟩",
-                                    'Y' => "🟨",
-                                    _ => "⬜",
-                                }
-                            );
-                        }
-                        println!();
+                        println!("  {}. {} → {:#}", i + 1, attempt.word, attempt.feedback);
                     }
                 }
 
-                if state.candidates.len() <= 20 && state.candidates.len() > 0 {
+                if state.candidates.len() <= 20 && !state.candidates.is_empty() {
                     println!("\n💡 Current candidates:");
-                    for chunk in state.candidates.chunks(5) {
+                    for chunk in ranked_candidates(&state.candidates, dict).chunks(5) {
                         println!("  {}", chunk.join(", "));
                     }
                 }
@@ -113,23 +164,43 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
             }
         }
 
+        // A pasted share block replaces the game so far.
+        if share::is_share_text(input) {
+            match read_share(input, dict) {
+                Ok((record, rows)) => {
+                    let attempts: Vec<Guess> = rows.iter().map(|r| r.guess.clone()).collect();
+                    state = replay(&attempts, dict, hard_mode || record.hard_mode);
+                    pending_fixes.clear();
+                    if record.solved {
+                        println!("\n🎉 Solved in {} attempts!", record.attempts());
+                        println!("✨ The word was: {}", attempts[attempts.len() - 1].word);
+                        break;
+                    }
+                    println!("\n📝 Candidates remaining: {}", state.candidates.len());
+                    suggestions = print_suggestions(solver.as_ref(), &state, dict, "next");
+                }
+                Err(e) => println!("❌ {}", e),
+            }
+            println!();
+            continue;
+        }
+
+        // Only a contradiction leaves the game running after the last attempt.
+        if state.attempt_count >= MAX_ATTEMPTS {
+            println!(
+                "😔 No attempts left: use /fix N to correct the feedback, or /r to start over.\n"
+            );
+            continue;
+        }
+
         // Parse the input
-        match core::parse_input(input) {
+        match core::parse_input(input, dict.word_len()) {
             Ok(guess) => {
                 // Display feedback visualization
-                print!("📊 Your feedback: ");
-                for &fb in guess.feedback.iter() {
-                    let symbol = match fb {
-                        'G' => "🟩",
-                        'Y' => "🟨",
-                        _ => "⬜",
-                    };
-                    print!("{}", symbol);
-                }
-                println!();
+                println!("📊 Your feedback: {:#}", guess.feedback);
 
                 // Check for win
-                if guess.feedback.iter().all(|&c| c == 'G') {
+                if guess.feedback.is_all_green() {
                     println!(
                         "\n🎉 Congratulations! You solved it in {} attempts!",
                         state.attempt_count + 1
@@ -138,56 +209,57 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
                     break;
                 }
 
+                if let Some(rule) = state.hard_mode_violation(&guess.word) {
+                    println!("⚠️  Not a valid hard-mode guess: {}", rule);
+                }
+
                 // Update state and filter candidates
                 state.add_attempt(guess.clone());
-                core::filter_words(&mut state.candidates, &guess);
+                suggestions.clear();
+                dict.filter(&mut state.candidates, &guess);
+                let remaining = ranked_candidates(&state.candidates, dict);
 
-                println!("\n📝 Candidates remaining: {}", state.candidates.len());
+                println!("\n📝 Candidates remaining: {}", remaining.len());
 
-                if state.candidates.is_empty() {
+                if remaining.is_empty() {
                     println!(
                         "❌ No candidates left! Check your input or the word might not be in our list."
                     );
-                    break;
-                } else if state.candidates.len() == 1 {
-                    println!("🎯 Only one possibility left: {}", state.candidates[0]);
+                    pending_fixes = report_contradiction(&state.attempts, dict);
+                    println!();
+                    continue;
+                } else if remaining.len() == 1 {
+                    println!("🎯 Only one possibility left: {}", remaining[0]);
                     println!("💡 Try this word next!");
-                } else if state.candidates.len() <= 20 {
+                } else if remaining.len() <= 20 {
                     println!("💡 Possible words:");
-                    for chunk in state.candidates.chunks(10) {
+                    for chunk in remaining.chunks(10) {
                         println!("   {}", chunk.join(", "));
                     }
 
-                    // Use solver for suggestion
-                    let suggestion = solver.suggest_guess(&state, &all_words);
-                    println!("\n💡 Suggested next guess: {}", suggestion);
-                } else if state.candidates.len() <= 200 {
+                    println!();
+                    suggestions = print_suggestions(solver.as_ref(), &state, dict, "next");
+                } else if remaining.len() <= 200 {
                     println!("💡 Top candidates:");
-                    for chunk in state.candidates[..20.min(state.candidates.len())].chunks(10) {
+                    for chunk in remaining[..20.min(remaining.len())].chunks(10) {
                         println!("   {}", chunk.join(", "));
                     }
-                    println!("   ... and {} more", state.candidates.len() - 20);
+                    println!("   ... and {} more", remaining.len() - 20);
 
-                    // Use solver for suggestion
-                    let suggestion = solver.suggest_guess(&state, &all_words);
-                    println!("\n💡 Suggested next guess: {}", suggestion);
+                    println!();
+                    suggestions = print_suggestions(solver.as_ref(), &state, dict, "next");
                 } else {
                     println!(
                         "💡 Too many candidates to display ({} words)",
-                        state.candidates.len()
+                        remaining.len()
                     );
-                    // Use solver for suggestion
-                    let suggestion = solver.suggest_guess(&state, &all_words);
-                    println!("💡 Suggested next guess: {}", suggestion);
+                    suggestions = print_suggestions(solver.as_ref(), &state, dict, "next");
                 }
 
-                if state.attempt_count >= 6 {
+                if state.attempt_count >= MAX_ATTEMPTS {
                     println!("\n😔 Reached maximum attempts!");
-                    if state.candidates.len() <= 10 {
-                        println!(
-                            "The word was likely one of: {}",
-                            state.candidates.join(", ")
-                        );
+                    if remaining.len() <= 10 {
+                        println!("The word was likely one of: {}", remaining.join(", "));
                     }
                     break;
                 }
@@ -203,31 +275,360 @@ pub fn run_solver_mode(solver: Box<dyn SolverStrategy>) {
     }
 }
 
-pub fn run_game_mode() {
+/// Attempts in a game.
+const MAX_ATTEMPTS: usize = 6;
+
+/// Ranked suggestions shown after each attempt.
+const SHOWN_SUGGESTIONS: usize = 5;
+
+/// The solver's best guess, followed by its runners-up and how each splits the
+/// candidates.
+fn print_suggestions(
+    solver: &dyn SolverStrategy,
+    state: &GameState,
+    dict: &Dictionary,
+    turn: &str,
+) -> Vec<Suggestion> {
+    let suggestions = solver.suggest(state, dict, SHOWN_SUGGESTIONS);
+    let Some(best) = suggestions.first() else {
+        println!("💡 Nothing left to suggest");
+        return suggestions;
+    };
+    println!("💡 Suggested {} guess: {}", turn, best.word);
+    print_endgame(state, dict, &best.word);
+    if let Some(p) = solver.solve_probability(state, dict, &best.word) {
+        println!("🛟 Chance to solve in time: {:.1}%", p * 100.0);
+    }
+    if suggestions.len() < 2 {
+        return suggestions;
+    }
+    let width = dict.word_len().max(4);
+    println!(
+        "   {:<width$}  {:>5}  {:>7}  {:>7}",
+        "Word", "Bits", "E[left]", "P(win)"
+    );
+    for s in &suggestions {
+        println!(
+            "   {:<width$}  {:>5.2}  {:>7.1}  {:>6.2}%{}",
+            s.word,
+            s.entropy,
+            s.expected_remaining,
+            s.p_win * 100.0,
+            if s.is_candidate {
+                "  ✓ candidate"
+            } else {
+                ""
+            }
+        );
+    }
+    suggestions
+}
+
+/// For small candidate sets, the most guesses best play can take from here.
+fn print_endgame(state: &GameState, dict: &Dictionary, suggested: &str) {
+    if state.candidates.len() < 2 || state.candidates.len() > ENDGAME_MAX {
+        return;
+    }
+    let Some(plan) = Endgame::new(dict, state.hard_mode).solve(&state.candidates) else {
+        return;
+    };
+    let guess = dict.word(plan.guess);
+    if guess == suggested {
+        println!(
+            "🎯 Solved within {} more guesses, {:.2} expected",
+            plan.max_guesses, plan.expected_guesses
+        );
+    } else {
+        println!(
+            "🎯 Best play ({} first) solves within {} more guesses, {:.2} expected",
+            guess, plan.max_guesses, plan.expected_guesses
+        );
+    }
+}
+
+/// How the last suggestion and its runners-up split the candidates, and the
+/// feedback groups of the suggestion itself.
+fn print_why(suggestions: &[Suggestion], state: &GameState, dict: &Dictionary) {
+    const EXAMPLES: usize = 3;
+    const MAX_BUCKETS: usize = 12;
+
+    let Some(chosen) = suggestions.first() else {
+        println!("❌ No suggestion to explain yet\n");
+        return;
+    };
+    let explanations: Vec<explain::Explanation> = suggestions
+        .iter()
+        .map(|s| explain::explain(&s.word, state, dict, EXAMPLES))
+        .collect();
+
+    println!(
+        "\n🤔 Why {}? Against {} candidates:",
+        chosen.word,
+        state.candidates.len()
+    );
+    let width = dict.word_len().max(4);
+    println!(
+        "   {:<width$}  {:>5}  {:>7}  {:>5}  {:>7}",
+        "Word", "Bits", "E[left]", "Worst", "P(win)"
+    );
+    for (n, e) in explanations.iter().enumerate() {
+        let s = &e.suggestion;
+        println!(
+            "   {:<width$}  {:>5.2}  {:>7.1}  {:>5}  {:>6.2}%{}{}",
+            s.word,
+            s.entropy,
+            s.expected_remaining,
+            e.worst_case,
+            s.p_win * 100.0,
+            if s.is_candidate {
+                "  ✓ candidate"
+            } else {
+                ""
+            },
+            if n == 0 { "  ← suggested" } else { "" }
+        );
+    }
+
+    if !chosen.is_candidate {
+        if let Some(best) = explain::best_candidate(state, dict) {
+            println!(
+                "\n💡 {} can't be the answer, but leaves {:.1} candidates on average; \
+                 the best possible answer, {}, leaves {:.1}.",
+                chosen.word, chosen.expected_remaining, best.word, best.expected_remaining
+            );
+        }
+    }
+
+    let buckets = &explanations[0].buckets;
+    println!(
+        "\n📊 {} splits the candidates into {} groups:",
+        chosen.word,
+        buckets.len()
+    );
+    for bucket in buckets.iter().take(MAX_BUCKETS) {
+        let more = bucket.size - bucket.examples.len();
+        println!(
+            "   {}  {:>4}  {}{}",
+            pattern_to_string(&bucket.pattern),
+            bucket.size,
+            bucket.examples.join(", "),
+            if more > 0 {
+                format!(", +{} more", more)
+            } else {
+                String::new()
+            }
+        );
+    }
+    if buckets.len() > MAX_BUCKETS {
+        println!("   ... and {} smaller groups", buckets.len() - MAX_BUCKETS);
+    }
+    println!();
+}
+
+/// Candidate words in dictionary order, or most likely first with their
+/// probability of being the answer when a frequency prior is loaded.
+fn ranked_candidates(candidates: &CandidateSet, dict: &Dictionary) -> Vec<String> {
+    let ids: Vec<usize> = candidates.iter().collect();
+    if dict.prior().is_uniform() || ids.len() < 2 {
+        return ids.iter().map(|&s| dict.word(s).to_string()).collect();
+    }
+    let mut ranked: Vec<(usize, f64)> = ids
+        .iter()
+        .copied()
+        .zip(dict.prior().probabilities(&ids))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+        .into_iter()
+        .map(|(s, p)| format!("{} ({:.1}%)", dict.word(s), p * 100.0))
+        .collect()
+}
+
+fn print_loaded(dict: &Dictionary) {
+    if dict.num_guesses() > dict.num_answers() {
+        println!(
+            "✅ Loaded {} {}-letter answers and {} allowed guesses\n",
+            dict.num_answers(),
+            dict.word_len(),
+            dict.num_guesses()
+        );
+    } else {
+        println!(
+            "✅ Loaded {} {}-letter words\n",
+            dict.num_answers(),
+            dict.word_len()
+        );
+    }
+}
+
+/// Read the grid rows after a pasted share `header`, then the words played,
+/// and review each row.
+fn read_share(header: &str, dict: &Dictionary) -> Result<(ShareRecord, Vec<RowAnalysis>), String> {
+    let mut text = format!("{}\n", header);
+    let mut seen_row = false;
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read input");
+        let row = line.trim();
+        if read == 0 || (seen_row && row.is_empty()) {
+            break;
+        }
+        if !row.is_empty() {
+            if row.parse::<Pattern>().is_err() {
+                break; // a link or comment after the grid
+            }
+            seen_row = true;
+            text.push_str(&line);
+        }
+    }
+    let record = share::parse_share(&text)?;
+    print_share_header(&record);
+
+    print!("✍️  Words played, one per row: ");
+    io::stdout().flush().unwrap();
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .expect("Failed to read input");
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let rows = analyzer::analyze_share(&record, &words, dict)?;
+
+    println!("\n    Guess   Feedback      Left   Bits (expected)");
+    for (i, row) in rows.iter().enumerate() {
+        println!(
+            "{:2}. {}   {:#}  {:5}   {:.2} ({:.2})",
+            i + 1,
+            row.guess.word,
+            row.guess.feedback,
+            row.after,
+            row.actual_bits,
+            row.expected_bits
+        );
+    }
+    Ok((record, rows))
+}
+
+/// "Wordle 1234: 4/6 (hard mode)"
+fn print_share_header(record: &ShareRecord) {
+    println!(
+        "\n🧾 {} {}: {}/{}{}",
+        record.game,
+        record.puzzle,
+        if record.solved {
+            record.attempts().to_string()
+        } else {
+            "X".to_string()
+        },
+        record.max_attempts,
+        if record.hard_mode { " (hard mode)" } else { "" }
+    );
+}
+
+/// A fresh state over the whole dictionary.
+fn new_state(dict: &Dictionary, hard_mode: bool) -> GameState {
+    let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+    state.hard_mode = hard_mode;
+    state
+}
+
+/// Rebuild the solver state from a list of attempts.
+fn replay(attempts: &[Guess], dict: &Dictionary, hard_mode: bool) -> GameState {
+    let mut state = new_state(dict, hard_mode);
+    for guess in attempts {
+        state.add_attempt(guess.clone());
+        dict.filter(&mut state.candidates, guess);
+    }
+    state
+}
+
+/// Explain which attempts conflict and list the fixes `/fix N` can apply.
+fn report_contradiction(attempts: &[Guess], dict: &Dictionary) -> Vec<FeedbackFix> {
+    const MAX_SHOWN: usize = 10;
+
+    let diagnosis = diagnose::diagnose(attempts, dict);
+    let numbered = |ids: &[usize]| {
+        ids.iter()
+            .map(|&i| format!("#{} {}", i + 1, attempts[i].word))
+            .collect::<Vec<_>>()
+            .join(" + ")
+    };
+
+    println!("\n🔎 Conflicting attempts:");
+    for conflict in &diagnosis.conflicts {
+        let reason = if conflict.impossible {
+            "the feedback contradicts itself"
+        } else {
+            "no word in the list fits"
+        };
+        println!("  {} ({})", numbered(&conflict.attempts), reason);
+    }
+
+    if diagnosis.fixes.is_empty() {
+        println!("\n💡 No small feedback correction helps; use /r to start over.");
+        return Vec::new();
+    }
+
+    println!("\n🔧 Possible corrections:");
+    let fixes: Vec<FeedbackFix> = diagnosis.fixes.into_iter().take(MAX_SHOWN).collect();
+    for (n, fix) in fixes.iter().enumerate() {
+        let edits: Vec<String> = fix
+            .edits
+            .iter()
+            .map(|e| {
+                format!(
+                    "#{} {} tile {} {}→{}",
+                    e.attempt + 1,
+                    attempts[e.attempt].word,
+                    e.position + 1,
+                    e.from.emoji(),
+                    e.to.emoji()
+                )
+            })
+            .collect();
+        let words = dict.words_in(&fix.candidates);
+        let example = words[..words.len().min(3)].join(", ");
+        println!(
+            "  {}. {} → {} candidates (e.g. {})",
+            n + 1,
+            edits.join(", "),
+            words.len(),
+            example
+        );
+    }
+    println!("\n💡 Type /fix N to apply a correction, or /r to start over.");
+    fixes
+}
+
+pub fn run_game_mode(dict: &Dictionary, hard_mode: bool) {
     println!("🎮 Wordle Game Mode");
     println!("==================");
 
-    // Load embedded word list
-    let mut candidates = core::load_words().expect("Failed to load embedded word list");
-    println!("✅ Loaded {} words\n", candidates.len());
+    let word_len = dict.word_len();
+    let mut state = new_state(dict, hard_mode);
+    print_loaded(dict);
 
     // Get the solution word
-    print!("Enter the solution word (5 letters): ");
+    print!("Enter the solution word ({} letters): ", word_len);
     io::stdout().flush().unwrap();
 
     let mut solution = String::new();
     io::stdin()
         .read_line(&mut solution)
         .expect("Failed to read solution");
-    let solution = solution.trim().to_uppercase();
+    let solution = normalize_word(&solution);
 
-    if solution.len() != 5 {
-        eprintln!("❌ Solution must be exactly 5 letters!");
+    if word_length(&solution) != word_len {
+        eprintln!("❌ Solution must be exactly {} letters!", word_len);
         return;
     }
 
     println!("\n🎯 Solution set! Let's start guessing.\n");
-    println!("📝 Candidates remaining: {}", candidates.len());
+    if hard_mode {
+        println!("🔒 Hard mode: revealed hints must be used in later guesses");
+    }
+    println!("📝 Candidates remaining: {}", state.candidates.len());
 
     let mut attempt = 1;
 
@@ -237,13 +638,24 @@ pub fn run_game_mode() {
         io::stdout().flush().unwrap();
 
         let mut guess_input = String::new();
-        io::stdin()
+        let read = io::stdin()
             .read_line(&mut guess_input)
             .expect("Failed to read guess");
-        let guess_word = guess_input.trim().to_uppercase();
+        if read == 0 {
+            break; // end of input
+        }
+        let guess_word = normalize_word(&guess_input);
 
-        if guess_word.len() != 5 {
-            println!("❌ Guess must be exactly 5 letters!");
+        if word_length(&guess_word) != word_len {
+            println!("❌ Guess must be exactly {} letters!", word_len);
+            continue;
+        }
+        if !dict.alphabet().spells(&guess_word) {
+            println!("❌ Guess uses letters that are not in this dictionary!");
+            continue;
+        }
+        if let Some(rule) = state.hard_mode_violation(&guess_word) {
+            println!("❌ {}", rule);
             continue;
         }
 
@@ -251,19 +663,10 @@ pub fn run_game_mode() {
         let feedback = core::calculate_feedback(&guess_word, &solution);
 
         // Display feedback
-        print!("📊 Feedback: ");
-        for &fb in feedback.iter() {
-            let symbol = match fb {
-                'G' => "🟩",
-                'Y' => "🟨",
-                _ => "⬜",
-            };
-            print!("{}", symbol);
-        }
-        println!(" ({})", feedback.iter().collect::<String>());
+        println!("📊 Feedback: {:#} ({})", feedback, feedback);
 
         // Check for win
-        if feedback.iter().all(|&c| c == 'G') {
+        if feedback.is_all_green() {
             println!("\n🎉 Congratulations! You found the word: {}", solution);
             println!("✨ Solved in {} attempts!", attempt);
             break;
@@ -271,22 +674,24 @@ pub fn run_game_mode() {
 
         // Filter candidates based on feedback
         let guess = Guess::new(guess_word.clone(), feedback);
-        core::filter_words(&mut candidates, &guess);
+        dict.filter(&mut state.candidates, &guess);
+        state.add_attempt(guess);
+        let remaining = dict.words_in(&state.candidates);
 
-        println!("\n📝 Candidates remaining: {}", candidates.len());
+        println!("\n📝 Candidates remaining: {}", remaining.len());
 
-        if candidates.is_empty() {
+        if remaining.is_empty() {
             println!("❌ No candidates left! Something went wrong.");
             break;
-        } else if candidates.len() <= 200 {
+        } else if remaining.len() <= 200 {
             println!("💡 Possible words:");
             // Display words in rows of 10 for better readability
-            for chunk in candidates.chunks(10) {
+            for chunk in remaining.chunks(10) {
                 println!("   {}", chunk.join(", "));
             }
         } else {
             println!("💡 First 100 candidates:");
-            for chunk in candidates[..100.min(candidates.len())].chunks(10) {
+            for chunk in remaining[..100.min(remaining.len())].chunks(10) {
                 println!("   {}", chunk.join(", "));
             }
         }
@@ -299,3 +704,187 @@ pub fn run_game_mode() {
         }
     }
 }
+
+/// Game mode against a host with no fixed answer, which dodges every guess by
+/// keeping the most candidates alive.
+pub fn run_evil_mode(dict: &Dictionary, hard_mode: bool, tie_break: TieBreak) {
+    println!("😈 Evil Wordle Mode");
+    println!("==================");
+    println!("I haven't picked a word. Every guess gets the feedback that keeps the");
+    println!("most words possible; corner me until only your guess is left.\n");
+
+    let word_len = dict.word_len();
+    let mut state = new_state(dict, hard_mode);
+    let mut host = AdversarialHost::new(dict, tie_break);
+    print_loaded(dict);
+    if hard_mode {
+        println!("🔒 Hard mode: revealed hints must be used in later guesses");
+    }
+    println!("📝 Words still possible: {}", host.candidates().len());
+
+    loop {
+        print!(
+            "\n🎲 Attempt #{}: Enter your guess (or /q to give up): ",
+            state.attempt_count + 1
+        );
+        io::stdout().flush().unwrap();
+
+        let mut guess_input = String::new();
+        let read = io::stdin()
+            .read_line(&mut guess_input)
+            .expect("Failed to read guess");
+        let guess_word = normalize_word(&guess_input);
+        if read == 0 || guess_word == "/Q" {
+            if let Some(word) = host.forced_answer() {
+                println!("\n😈 The word had to be: {}", word);
+            } else if let Some(s) = host.candidates().first() {
+                println!(
+                    "\n😈 I'd have picked {} (one of {} words left)",
+                    dict.word(s),
+                    host.candidates().len()
+                );
+            }
+            break;
+        }
+
+        if word_length(&guess_word) != word_len {
+            println!("❌ Guess must be exactly {} letters!", word_len);
+            continue;
+        }
+        if !dict.alphabet().spells(&guess_word) {
+            println!("❌ Guess uses letters that are not in this dictionary!");
+            continue;
+        }
+        if let Some(rule) = state.hard_mode_violation(&guess_word) {
+            println!("❌ {}", rule);
+            continue;
+        }
+
+        let feedback = host.respond(&guess_word);
+        println!("📊 Feedback: {:#} ({})", feedback, feedback);
+        state.add_attempt(Guess::new(guess_word.clone(), feedback));
+
+        if feedback.is_all_green() {
+            println!(
+                "\n🎉 You cornered me in {} attempts! The word had to be: {}",
+                state.attempt_count, guess_word
+            );
+            break;
+        }
+
+        let remaining = host.candidates().len();
+        match host.forced_answer() {
+            Some(word) => println!("\n📝 Only one word is left, so I'm stuck with it: {}", word),
+            None => println!("\n📝 Words still possible: {}", remaining),
+        }
+        if remaining > 1 && remaining <= 20 {
+            println!("💡 Possible words:");
+            for chunk in dict.words_in(host.candidates()).chunks(10) {
+                println!("   {}", chunk.join(", "));
+            }
+        }
+    }
+}
+
+pub fn run_reverse_mode(answer: &str, dict: &Dictionary) {
+    const SHOWN_WORDS: usize = 10;
+    const SHOWN_SEQUENCES: usize = 10;
+
+    println!("🔁 Reverse Solver");
+    println!("=================");
+
+    let answer = match reverse::answer_index(answer, dict) {
+        Ok(answer) => answer,
+        Err(e) => {
+            println!("❌ {}", e);
+            return;
+        }
+    };
+    println!("Paste the shared grid (rows on separate lines or split by '/')");
+    println!("or the whole share text, then an empty line:\n");
+
+    let mut text = String::new();
+    let mut seen_row = false;
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read input");
+        if read == 0 || (seen_row && line.trim().is_empty()) {
+            break;
+        }
+        seen_row |= line.trim().parse::<Pattern>().is_ok();
+        text.push_str(&line);
+    }
+
+    let mut hard_mode = false;
+    let rows = if share::is_share_text(&text) {
+        share::parse_share(&text).and_then(|record| {
+            print_share_header(&record);
+            hard_mode = record.hard_mode;
+            if record.rows[0].len() != dict.word_len() {
+                return Err(format!("The grid is not {} tiles wide", dict.word_len()));
+            }
+            Ok(record.rows)
+        })
+    } else {
+        reverse::parse_grid(&text, dict.word_len())
+    };
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            println!("❌ {}", e);
+            return;
+        }
+    };
+
+    println!(
+        "\n📝 Words that fit each row (answer {}):",
+        dict.word(answer)
+    );
+    for (i, &row) in rows.iter().enumerate() {
+        let words = reverse::row_words(row, answer, dict);
+        let shown: Vec<&str> = words
+            .iter()
+            .take(SHOWN_WORDS)
+            .map(|&g| dict.word(g))
+            .collect();
+        let more = if words.len() > SHOWN_WORDS {
+            ", ..."
+        } else {
+            ""
+        };
+        println!(
+            "  {}. {:#} {:4} words: {}{}",
+            i + 1,
+            row,
+            words.len(),
+            shown.join(", "),
+            more
+        );
+    }
+
+    let mut sequences = reverse::rank_sequences(&rows, answer, dict, SHOWN_SEQUENCES);
+    if hard_mode {
+        // The share says hard mode, so other sequences were never possible.
+        sequences.retain(|sequence| sequence.hard_mode);
+    }
+    if sequences.is_empty() {
+        println!("\n❌ No sequence of distinct dictionary words produces this grid.");
+        return;
+    }
+    println!("\n🏆 Most plausible guess sequences:");
+    for (i, sequence) in sequences.iter().enumerate() {
+        println!(
+            "  {:2}. {}  {:.2} bits{}",
+            i + 1,
+            sequence.words.join(" → "),
+            sequence.bits,
+            if sequence.hard_mode {
+                "  (hard mode)"
+            } else {
+                ""
+            }
+        );
+    }
+}
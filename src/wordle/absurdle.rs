@@ -0,0 +1,235 @@
+use crate::wordle::adversary::AdversarialHost;
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::{num_patterns, Pattern};
+use std::collections::{HashMap, HashSet};
+
+/// Finds the fewest guesses that corner an Absurdle-style host, which answers
+/// every guess with its largest feedback bucket. When several buckets tie, the
+/// host is assumed to pick whichever is worst for the player, so the result
+/// holds whatever its tie-break. Positions are memoized on their candidate set.
+///
+/// In hard mode only candidates are tried: they always satisfy the hints.
+pub struct AbsurdleSearch<'a> {
+    dict: &'a Dictionary,
+    hard_mode: bool,
+    progress: bool,
+    memo: HashMap<CandidateSet, Solved>,
+}
+
+/// What is known about a position.
+#[derive(Debug, Clone, Copy)]
+enum Solved {
+    /// Cornered within this many guesses, starting with the guess index.
+    Within(usize, usize),
+    /// Needs at least this many guesses.
+    AtLeast(usize),
+}
+
+/// One turn of a game against the host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    pub guess: String,
+    pub feedback: Pattern,
+    /// Candidates the host has left after the feedback.
+    pub left: usize,
+}
+
+/// A guess and the buckets the host may keep after it.
+struct Move {
+    guess: usize,
+    largest: usize,
+    kept: Vec<CandidateSet>,
+}
+
+impl<'a> AbsurdleSearch<'a> {
+    pub fn new(dict: &'a Dictionary, hard_mode: bool) -> Self {
+        AbsurdleSearch {
+            dict,
+            hard_mode,
+            progress: false,
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Fewest guesses that surely corner the host from `candidates`, the
+    /// winning one included, and the guess index to start with.
+    pub fn solve(&mut self, candidates: &CandidateSet) -> Option<(usize, usize)> {
+        if candidates.is_empty() {
+            return None;
+        }
+        // Guessing a candidate always removes it, so `len` guesses suffice.
+        for turns in self.lower_bound(candidates.len())..=candidates.len() {
+            if self.progress {
+                eprint!("\r  Trying {} guesses...", turns);
+            }
+            if let Some(g) = self.within(candidates, turns) {
+                if self.progress {
+                    eprintln!();
+                }
+                return Some((turns, g));
+            }
+        }
+        unreachable!("guessing the candidates one by one always corners the host")
+    }
+
+    /// Up to `limit` guess indices that corner the host from `candidates` in
+    /// the fewest guesses, with that number.
+    pub fn best_guesses(&mut self, candidates: &CandidateSet, limit: usize) -> (usize, Vec<usize>) {
+        let Some((turns, first)) = self.solve(candidates) else {
+            return (0, Vec::new());
+        };
+        let mut best = vec![first];
+        if candidates.len() > 1 {
+            for m in self.moves(candidates) {
+                if best.len() >= limit {
+                    break;
+                }
+                if m.guess != first && self.corners(&m, turns - 1) {
+                    best.push(m.guess);
+                }
+            }
+        }
+        best.truncate(limit);
+        (turns, best)
+    }
+
+    /// Best play against `host` from where it stands, with its replies, up to
+    /// the all-green one. No progress is reported: call `solve` first for that,
+    /// and the replay reads its results from the memo.
+    pub fn play_out(&mut self, host: &mut AdversarialHost) -> Vec<Turn> {
+        let progress = std::mem::replace(&mut self.progress, false);
+        let mut turns = Vec::new();
+        while let Some((_, g)) = self.solve(host.candidates()) {
+            let guess = self.dict.word(g).to_string();
+            let feedback = host.respond(&guess);
+            turns.push(Turn {
+                guess,
+                feedback,
+                left: host.candidates().len(),
+            });
+            if feedback.is_all_green() {
+                break;
+            }
+        }
+        self.progress = progress;
+        turns
+    }
+
+    /// A guess that corners the host from `cands` within `turns` guesses.
+    fn within(&mut self, cands: &CandidateSet, turns: usize) -> Option<usize> {
+        let n = cands.len();
+        if n == 1 {
+            return cands.first().filter(|_| turns >= 1);
+        }
+        if turns < self.lower_bound(n) {
+            return None;
+        }
+        match self.memo.get(cands) {
+            Some(&Solved::Within(k, g)) if k <= turns => return Some(g),
+            Some(&Solved::AtLeast(k)) if turns < k => return None,
+            _ => {}
+        }
+
+        let mut found = None;
+        if turns == 2 {
+            // The host must be left one word whatever it says.
+            found = self.pool(cands).find(|&g| self.splits_apart(g, cands));
+        } else {
+            for m in self.moves(cands) {
+                if self.lower_bound(m.largest) > turns - 1 {
+                    // Moves come smallest bucket first; none of the rest can do it.
+                    break;
+                }
+                if self.corners(&m, turns - 1) {
+                    found = Some(m.guess);
+                    break;
+                }
+            }
+        }
+        let solved = match found {
+            Some(g) => Solved::Within(turns, g),
+            None => Solved::AtLeast(turns + 1),
+        };
+        self.memo.insert(cands.clone(), solved);
+        found
+    }
+
+    /// Every bucket the host may keep after `m` is cornered within `turns`.
+    fn corners(&mut self, m: &Move, turns: usize) -> bool {
+        m.kept.iter().all(|kept| self.within(kept, turns).is_some())
+    }
+
+    /// Guesses for `cands` (at least two), with the buckets the host may keep,
+    /// smallest largest bucket first. Guesses leaving the host the same choice
+    /// as an earlier one are left out.
+    fn moves(&self, cands: &CandidateSet) -> Vec<Move> {
+        let dict = self.dict;
+        let all_green = Pattern::all_green(dict.word_len()).id();
+        let mut seen: HashSet<Vec<CandidateSet>> = HashSet::new();
+        let mut moves = Vec::new();
+        for g in self.pool(cands) {
+            let sizes = dict.bucket_sizes(cands, g);
+            let largest = sizes.iter().map(|&(_, c)| c).max().unwrap_or(0);
+            // The host only concedes all green when nothing else is left.
+            let mut kept: Vec<CandidateSet> = sizes
+                .iter()
+                .filter(|&&(pid, c)| c == largest && pid != all_green)
+                .map(|&(pid, _)| {
+                    let mut kept = dict.masks(g)[&pid].clone();
+                    kept.intersect_with(cands);
+                    kept
+                })
+                .collect();
+            if largest == cands.len() {
+                continue; // splits nothing
+            }
+            kept.sort_by_key(|k| k.first());
+            if seen.insert(kept.clone()) {
+                moves.push(Move {
+                    guess: g,
+                    largest,
+                    kept,
+                });
+            }
+        }
+        moves.sort_by_key(|m| (m.largest, m.kept.len()));
+        moves
+    }
+
+    /// Guesses worth trying for `cands`.
+    fn pool<'c>(&self, cands: &'c CandidateSet) -> Box<dyn Iterator<Item = usize> + 'c> {
+        if self.hard_mode {
+            Box::new(cands.iter())
+        } else {
+            Box::new(0..self.dict.num_guesses())
+        }
+    }
+
+    /// `g` gives every candidate a different feedback.
+    fn splits_apart(&self, g: usize, cands: &CandidateSet) -> bool {
+        let table = self.dict.table();
+        let mut seen = vec![false; num_patterns(self.dict.word_len())];
+        cands
+            .iter()
+            .all(|s| !std::mem::replace(&mut seen[table.get(g, s)], true))
+    }
+
+    /// Fewest guesses that could corner the host from `n` candidates: each
+    /// guess leaves at least a `num_patterns`-th of them.
+    fn lower_bound(&self, n: usize) -> usize {
+        let buckets = num_patterns(self.dict.word_len());
+        let mut turns = 1;
+        let mut left = n;
+        while left > 1 {
+            left = left.div_ceil(buckets);
+            turns += 1;
+        }
+        turns
+    }
+}
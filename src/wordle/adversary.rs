@@ -0,0 +1,140 @@
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::core::calculate_feedback;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::solver::bucket_counts;
+use crate::wordle::types::{FeedbackType, Guess};
+
+/// How the host picks among feedback buckets of the same, largest size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Keep the bucket whose best next guess still leaves the most candidates
+    /// in the worst case.
+    #[default]
+    WorstCase,
+    /// Keep the bucket whose best next guess still leaves the most candidates
+    /// on average.
+    ExpectedCase,
+}
+
+impl TieBreak {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "worst" => Some(TieBreak::WorstCase),
+            "expected" => Some(TieBreak::ExpectedCase),
+            _ => None,
+        }
+    }
+}
+
+/// An Absurdle-style host: it commits to no answer, and after each guess keeps
+/// the largest group of candidates that share a feedback.
+#[derive(Clone)]
+pub struct AdversarialHost<'a> {
+    dict: &'a Dictionary,
+    pub tie_break: TieBreak,
+    candidates: CandidateSet,
+}
+
+impl<'a> AdversarialHost<'a> {
+    pub fn new(dict: &'a Dictionary, tie_break: TieBreak) -> Self {
+        AdversarialHost {
+            dict,
+            tie_break,
+            candidates: CandidateSet::full(dict.num_answers()),
+        }
+    }
+
+    /// Answers still consistent with every feedback given.
+    pub fn candidates(&self) -> &CandidateSet {
+        &self.candidates
+    }
+
+    /// The answer once the host has no choice left.
+    pub fn forced_answer(&self) -> Option<&str> {
+        match self.candidates.len() {
+            1 => self.candidates.first().map(|s| self.dict.word(s)),
+            _ => None,
+        }
+    }
+
+    /// Feedback for `word` that keeps the most candidates alive, and narrow the
+    /// candidates to it. All green only comes back when nothing else is left.
+    pub fn respond(&mut self, word: &str) -> Pattern {
+        let feedback = choose_feedback(self.dict, &self.candidates, word, self.tie_break);
+        self.dict.filter(
+            &mut self.candidates,
+            &Guess::new(word.to_string(), feedback),
+        );
+        feedback
+    }
+}
+
+/// The feedback an adversarial host gives `word` against `candidates`: the
+/// largest bucket, ties broken by `tie_break`, then by the fewest greens and
+/// yellows revealed.
+pub fn choose_feedback(
+    dict: &Dictionary,
+    candidates: &CandidateSet,
+    word: &str,
+    tie_break: TieBreak,
+) -> Pattern {
+    let word_len = dict.word_len();
+    let histogram = match dict.index_of(word) {
+        Some(g) => dict.bucket_sizes(candidates, g),
+        None => bucket_counts(
+            candidates
+                .iter()
+                .map(|s| calculate_feedback(word, dict.word(s)).id()),
+            word_len,
+        ),
+    };
+    let largest = histogram.iter().map(|&(_, n)| n).max().unwrap_or(0);
+    let tied: Vec<Pattern> = histogram
+        .iter()
+        .filter(|&&(_, n)| n == largest)
+        .map(|&(pid, _)| Pattern::from_id(pid, word_len))
+        .collect();
+    if tied.len() == 1 {
+        return tied[0];
+    }
+
+    let scored: Vec<(f64, Pattern)> = tied
+        .into_iter()
+        .map(|pattern| {
+            let mut bucket = candidates.clone();
+            dict.filter(&mut bucket, &Guess::new(word.to_string(), pattern));
+            (hardness(dict, &bucket, tie_break), pattern)
+        })
+        .collect();
+    let hardest = scored.iter().map(|&(h, _)| h).fold(f64::MIN, f64::max);
+    scored
+        .into_iter()
+        .filter(|&(h, _)| h >= hardest - 1e-9)
+        .map(|(_, pattern)| pattern)
+        .min_by_key(|&pattern| (hints(pattern), pattern.id()))
+        .expect("at least one bucket is tied for largest")
+}
+
+/// Candidates the best next guess still leaves in `bucket`: in its largest
+/// feedback group, or on average.
+fn hardness(dict: &Dictionary, bucket: &CandidateSet, tie_break: TieBreak) -> f64 {
+    let n = bucket.len() as f64;
+    (0..dict.num_guesses())
+        .map(|g| {
+            let sizes = dict.bucket_sizes(bucket, g);
+            match tie_break {
+                TieBreak::WorstCase => sizes.iter().map(|&(_, c)| c).max().unwrap_or(0) as f64,
+                TieBreak::ExpectedCase => {
+                    sizes.iter().map(|&(_, c)| (c * c) as f64).sum::<f64>() / n
+                }
+            }
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Greens, then yellows, in `pattern`.
+fn hints(pattern: Pattern) -> (usize, usize) {
+    let count = |kind| pattern.tiles().filter(|&t| t == kind).count();
+    (count(FeedbackType::Green), count(FeedbackType::Yellow))
+}
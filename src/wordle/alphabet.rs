@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+/// The letters a dictionary is spelled with, each mapped to a dense index.
+///
+/// A letter is one grapheme. Words are normalized first (see `normalize_word`) so
+/// that a decomposed "N + ◌̃" and a precomposed "Ñ" are the same letter, and every
+/// letter is then a single `char`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    letters: Vec<char>,
+    index: HashMap<char, usize>,
+}
+
+impl Alphabet {
+    /// Build from an explicit letter list; duplicates are dropped, order is kept.
+    pub fn new<I: IntoIterator<Item = char>>(letters: I) -> Self {
+        let mut alphabet = Alphabet {
+            letters: Vec::new(),
+            index: HashMap::new(),
+        };
+        for c in letters {
+            alphabet.push(c);
+        }
+        alphabet
+    }
+
+    /// A to Z.
+    pub fn english() -> Self {
+        Self::new('A'..='Z')
+    }
+
+    /// A to Z followed by every other letter used in `words`, in code point order.
+    pub fn from_words(words: &[String]) -> Self {
+        let mut alphabet = Self::english();
+        let mut extra: Vec<char> = words
+            .iter()
+            .flat_map(|w| w.chars())
+            .filter(|c| !alphabet.contains(*c))
+            .collect();
+        extra.sort_unstable();
+        for c in extra {
+            alphabet.push(c);
+        }
+        alphabet
+    }
+
+    fn push(&mut self, c: char) {
+        if !self.index.contains_key(&c) {
+            self.index.insert(c, self.letters.len());
+            self.letters.push(c);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.index.contains_key(&c)
+    }
+
+    /// Dense index of a letter.
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        self.index.get(&c).copied()
+    }
+
+    pub fn letter(&self, i: usize) -> char {
+        self.letters[i]
+    }
+
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    /// True when every letter of `word` belongs to this alphabet.
+    pub fn spells(&self, word: &str) -> bool {
+        word.chars().all(|c| self.contains(c))
+    }
+}
+
+/// Trim, compose common decomposed accents and uppercase letter by letter.
+///
+/// Uppercasing is per letter so that a letter whose uppercase form is several
+/// characters (ß → SS) stays a single tile.
+pub fn normalize_word(word: &str) -> String {
+    let mut out: Vec<char> = Vec::new();
+    for c in word.trim().chars() {
+        if let Some(composed) = out.last().and_then(|&base| compose(base, c)) {
+            *out.last_mut().unwrap() = composed;
+        } else {
+            out.push(c);
+        }
+    }
+    out.into_iter().map(uppercase_letter).collect()
+}
+
+/// Number of letters (graphemes) in a normalized word.
+pub fn word_length(word: &str) -> usize {
+    word.chars().count()
+}
+
+fn uppercase_letter(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+/// Precomposed form of `base` followed by a combining mark, for the accents
+/// used by Latin-script Wordle variants.
+fn compose(base: char, mark: char) -> Option<char> {
+    const TABLE: &[(char, char, char)] = &[
+        // combining tilde
+        ('N', '\u{303}', 'Ñ'),
+        ('n', '\u{303}', 'ñ'),
+        ('A', '\u{303}', 'Ã'),
+        ('a', '\u{303}', 'ã'),
+        ('O', '\u{303}', 'Õ'),
+        ('o', '\u{303}', 'õ'),
+        // combining diaeresis
+        ('A', '\u{308}', 'Ä'),
+        ('a', '\u{308}', 'ä'),
+        ('O', '\u{308}', 'Ö'),
+        ('o', '\u{308}', 'ö'),
+        ('U', '\u{308}', 'Ü'),
+        ('u', '\u{308}', 'ü'),
+        ('E', '\u{308}', 'Ë'),
+        ('e', '\u{308}', 'ë'),
+        ('I', '\u{308}', 'Ï'),
+        ('i', '\u{308}', 'ï'),
+        // combining acute
+        ('A', '\u{301}', 'Á'),
+        ('a', '\u{301}', 'á'),
+        ('E', '\u{301}', 'É'),
+        ('e', '\u{301}', 'é'),
+        ('I', '\u{301}', 'Í'),
+        ('i', '\u{301}', 'í'),
+        ('O', '\u{301}', 'Ó'),
+        ('o', '\u{301}', 'ó'),
+        ('U', '\u{301}', 'Ú'),
+        ('u', '\u{301}', 'ú'),
+        // combining grave
+        ('A', '\u{300}', 'À'),
+        ('a', '\u{300}', 'à'),
+        ('E', '\u{300}', 'È'),
+        ('e', '\u{300}', 'è'),
+        // combining circumflex
+        ('A', '\u{302}', 'Â'),
+        ('a', '\u{302}', 'â'),
+        ('E', '\u{302}', 'Ê'),
+        ('e', '\u{302}', 'ê'),
+        ('O', '\u{302}', 'Ô'),
+        ('o', '\u{302}', 'ô'),
+        // combining cedilla
+        ('C', '\u{327}', 'Ç'),
+        ('c', '\u{327}', 'ç'),
+    ];
+    TABLE
+        .iter()
+        .find(|&&(b, m, _)| b == base && m == mark)
+        .map(|&(_, _, composed)| composed)
+}
@@ -1,35 +1,75 @@
-use crate::wordle::core::load_words;
-use std::collections::HashSet;
-
-const WORD_LEN: usize = 5;
-const NUM_PATTERNS: usize = 243; // 3^5
-const PID_ALL_GREEN: usize = 242; // [2,2,2,2,2] in base-3
+use crate::wordle::alphabet::{normalize_word, word_length};
+use crate::wordle::candidates::CandidateSet;
+
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::share::ShareRecord;
+use crate::wordle::solver::{
+    bucket_counts, score_against, EntropyMaximizer, SolverStrategy, Suggestion,
+};
+use crate::wordle::types::{GameState, Guess};
+use std::collections::{HashMap, HashSet};
+
+/// Opener statistics: (entropy, expected remaining, P(win), pattern buckets by size).
+pub type OpenerAnalysis = (f64, f64, f64, Vec<(Pattern, usize)>);
+
+/// How one row of a shared game narrowed the answers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowAnalysis {
+    pub guess: Guess,
+    /// Answers still possible before and after the row.
+    pub before: usize,
+    pub after: usize,
+    /// Bits the guess was expected to give, and bits its feedback gave.
+    pub expected_bits: f64,
+    pub actual_bits: f64,
+}
 
 /// Analyzes the word list to find the best starting words
 pub struct StartingWordAnalyzer {
-    words: Vec<String>,
+    dict: Dictionary,
 }
 
 impl StartingWordAnalyzer {
     pub fn new() -> Result<Self, std::io::Error> {
-        let words = load_words()?;
-        Ok(Self { words })
+        Ok(Self::from_dictionary(Dictionary::embedded()?))
     }
 
     pub fn from_words(words: Vec<String>) -> Self {
-        Self { words }
+        Self::from_dictionary(Dictionary::new(words))
+    }
+
+    pub fn from_dictionary(dict: Dictionary) -> Self {
+        Self { dict }
+    }
+
+    /// Hand the dictionary back, e.g. to attach the book built from it.
+    pub fn into_dictionary(self) -> Dictionary {
+        self.dict
+    }
+
+    /// Possible answers the openers are scored against.
+    pub fn answers(&self) -> &[String] {
+        self.dict.answers()
     }
 
     /// Analyze all words and return the top N best starting words
-    pub fn find_best_starters(&self, top_n: usize) -> Vec<(String, f64, f64, f64)> {
-        let mut scores: Vec<(String, f64, f64, f64)> = Vec::new();
-        let total = self.words.len();
+    pub fn find_best_starters(&self, top_n: usize) -> Vec<Suggestion> {
+        let mut scores: Vec<Suggestion> = Vec::new();
+        let total = self.dict.num_guesses();
         let mut processed = 0;
 
-        // Consider all words as potential first guesses
-        for (i, guess) in self.words.iter().enumerate() {
-            let (entropy, exp_remaining, p_win) = self.score_as_opener(guess);
-            scores.push((guess.clone(), entropy, exp_remaining, p_win));
+        // Consider all allowed guesses as potential first guesses
+        for (i, guess) in self.dict.guesses().iter().enumerate() {
+            let row = self.dict.table().row(i);
+            let (entropy, exp_remaining, p_win) = score_against(row.enumerate(), &self.dict);
+            scores.push(Suggestion {
+                word: guess.clone(),
+                entropy,
+                expected_remaining: exp_remaining,
+                p_win,
+                is_candidate: i < self.dict.num_answers(),
+            });
 
             processed += 1;
             if processed % 100 == 0 {
@@ -45,10 +85,15 @@ impl StartingWordAnalyzer {
 
         // Sort by entropy (descending), then by expected remaining (ascending)
         scores.sort_by(|a, b| {
-            b.1.partial_cmp(&a.1)
+            b.entropy
+                .partial_cmp(&a.entropy)
                 .unwrap()
-                .then(a.2.partial_cmp(&b.2).unwrap())
-                .then(b.3.partial_cmp(&a.3).unwrap())
+                .then(
+                    a.expected_remaining
+                        .partial_cmp(&b.expected_remaining)
+                        .unwrap(),
+                )
+                .then(b.p_win.partial_cmp(&a.p_win).unwrap())
         });
 
         scores.into_iter().take(top_n).collect()
@@ -56,44 +101,22 @@ impl StartingWordAnalyzer {
 
     /// Calculate the entropy of a word as an opening guess
     fn score_as_opener(&self, guess: &str) -> (f64, f64, f64) {
-        let mut hist = [0usize; NUM_PATTERNS];
-
-        // Calculate feedback distribution
-        for solution in &self.words {
-            let pid = feedback_id(guess, solution);
-            hist[pid] += 1;
-        }
-
-        // Calculate entropy and expected remaining
-        let n = self.words.len() as f64;
-        let mut entropy = 0.0;
-        let mut sum_sq: usize = 0;
-
-        for &count in &hist {
-            if count > 0 {
-                let p = count as f64 / n;
-                entropy -= p * p.log2(); // Shannon entropy
-                sum_sq += count * count;
-            }
-        }
-
-        let exp_remaining = sum_sq as f64 / n;
-        let p_win = hist[PID_ALL_GREEN] as f64 / n;
-
-        (entropy, exp_remaining, p_win)
+        let row = self.dict.pattern_row(guess);
+        score_against(row.into_iter().enumerate(), &self.dict)
     }
 
     /// Find words with the most unique letter positions (good for gathering info)
     pub fn find_diverse_starters(&self, top_n: usize) -> Vec<(String, usize, f64)> {
         let mut scores: Vec<(String, usize, f64)> = Vec::new();
 
-        for word in &self.words {
+        let letter_freqs = self.calculate_letter_frequencies();
+
+        for word in self.dict.guesses() {
             let chars: HashSet<char> = word.chars().collect();
             let unique_count = chars.len();
 
             // Calculate position diversity bonus
             let mut position_score = 0.0;
-            let letter_freqs = self.calculate_letter_frequencies();
 
             for (i, ch) in word.chars().enumerate() {
                 if let Some(&freq) = letter_freqs.get(&(ch, i)) {
@@ -110,113 +133,168 @@ impl StartingWordAnalyzer {
         scores.into_iter().take(top_n).collect()
     }
 
-    fn calculate_letter_frequencies(&self) -> std::collections::HashMap<(char, usize), f64> {
-        use std::collections::HashMap;
-        let mut freq_map: HashMap<(char, usize), usize> = HashMap::new();
-        let total = self.words.len() as f64;
-
-        for word in &self.words {
-            for (i, ch) in word.chars().enumerate() {
-                *freq_map.entry((ch, i)).or_insert(0) += 1;
-            }
-        }
-
-        freq_map
-            .into_iter()
-            .map(|(key, count)| (key, count as f64 / total))
-            .collect()
+    /// Share of the possible answers with each letter at each position.
+    pub fn calculate_letter_frequencies(&self) -> HashMap<(char, usize), f64> {
+        letter_frequencies(self.dict.answers().iter().map(String::as_str))
     }
 
     /// Analyze a specific word as an opener
-    pub fn analyze_word(&self, word: &str) -> Option<(f64, f64, f64, Vec<(usize, usize)>)> {
-        if word.len() != 5 {
+    pub fn analyze_word(&self, word: &str) -> Option<OpenerAnalysis> {
+        let word_upper = normalize_word(word);
+        if word_length(&word_upper) != self.dict.word_len() {
             return None;
         }
 
-        let word_upper = word.to_uppercase();
-        if !self.words.contains(&word_upper) {
+        if self.dict.index_of(&word_upper).is_none() {
             eprintln!("Warning: '{}' is not in the word list", word);
         }
 
         let (entropy, exp_remaining, p_win) = self.score_as_opener(&word_upper);
 
         // Get pattern distribution
-        let mut pattern_dist = Vec::new();
-        let mut hist = [0usize; NUM_PATTERNS];
+        let buckets = match self.dict.index_of(&word_upper) {
+            Some(g) => self
+                .dict
+                .bucket_sizes(&CandidateSet::full(self.dict.num_answers()), g),
+            None => bucket_counts(
+                self.dict.pattern_row(&word_upper).into_iter(),
+                self.dict.word_len(),
+            ),
+        };
+
+        let mut pattern_dist: Vec<(Pattern, usize)> = buckets
+            .into_iter()
+            .map(|(pid, count)| (Pattern::from_id(pid, self.dict.word_len()), count))
+            .collect();
+        pattern_dist.sort_by_key(|&(p, count)| (std::cmp::Reverse(count), p.id()));
+
+        Some((entropy, exp_remaining, p_win, pattern_dist))
+    }
 
-        for solution in &self.words {
-            let pid = feedback_id(&word_upper, solution);
-            hist[pid] += 1;
+    /// Answers expected to remain after `opener` and then the reply for its
+    /// feedback, Σ p·|group| over the groups of answers sharing both feedbacks
+    /// as in `score_weighted`, with p the group's share of the prior.
+    pub fn expected_remaining_after(&self, opener: &str, replies: &[(Pattern, String)]) -> f64 {
+        let opener_row = self.dict.pattern_row(&normalize_word(opener));
+        let all: Vec<usize> = (0..self.dict.num_answers()).collect();
+        let probs = self.dict.prior().probabilities(&all);
+        let reply_rows: HashMap<usize, Vec<usize>> = replies
+            .iter()
+            .map(|(pattern, reply)| (pattern.id(), self.dict.pattern_row(reply)))
+            .collect();
+
+        // (probability, answers) per group
+        let mut groups: HashMap<(usize, usize), (f64, usize)> = HashMap::new();
+        for (s, &p) in probs.iter().enumerate() {
+            let first = opener_row[s];
+            let second = reply_rows.get(&first).map_or(0, |row| row[s]);
+            let group = groups.entry((first, second)).or_default();
+            group.0 += p;
+            group.1 += 1;
         }
+        groups.values().map(|&(p, c)| p * c as f64).sum()
+    }
 
-        for (pid, &count) in hist.iter().enumerate() {
-            if count > 0 {
-                pattern_dist.push((pid, count));
-            }
+    /// The entropy solver's second guess after each feedback `opener` can get.
+    pub fn second_guesses(&self, opener: &str) -> Vec<(Pattern, String)> {
+        let opener = normalize_word(opener);
+        let word_len = self.dict.word_len();
+        let mut buckets: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (s, pid) in self.dict.pattern_row(&opener).into_iter().enumerate() {
+            buckets.entry(pid).or_default().push(s);
         }
-        pattern_dist.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
 
-        Some((entropy, exp_remaining, p_win, pattern_dist))
+        let mut replies: Vec<(Pattern, String)> = buckets
+            .into_iter()
+            .map(|(pid, bucket)| (Pattern::from_id(pid, word_len), bucket))
+            .filter(|(pattern, _)| !pattern.is_all_green())
+            .map(|(pattern, bucket)| {
+                let candidates = CandidateSet::from_indices(self.dict.num_answers(), bucket);
+                let mut state = GameState::new(candidates, self.dict.alphabet());
+                state.add_attempt(Guess::new(opener.clone(), pattern));
+                let reply = EntropyMaximizer::default().suggest_guess(&state, &self.dict);
+                (pattern, reply)
+            })
+            .collect();
+        replies.sort_by_key(|(p, _)| p.id());
+        replies
     }
 }
 
-/// Return Wordle feedback encoded in base-3 (same as in solver.rs)
-#[inline]
-fn feedback_id(guess: &str, solution: &str) -> usize {
-    let g = guess.as_bytes();
-    let s = solution.as_bytes();
-
-    if g.len() != WORD_LEN || s.len() != WORD_LEN {
-        return 0;
+/// Replay a shared game with the `words` that were played, one per row, and
+/// score each guess against the answers still possible before it.
+pub fn analyze_share(
+    record: &ShareRecord,
+    words: &[&str],
+    dict: &Dictionary,
+) -> Result<Vec<RowAnalysis>, String> {
+    if record.rows[0].len() != dict.word_len() {
+        return Err(format!("The grid is not {} tiles wide", dict.word_len()));
     }
-
-    let mut trits = [0u8; WORD_LEN];
-    let mut counts = [0i8; 26];
-
-    // Pass 1: greens + count non-green solution letters
-    for i in 0..WORD_LEN {
-        if g[i] == s[i] {
-            trits[i] = 2; // green
-        } else {
-            let idx = (s[i].to_ascii_uppercase() - b'A') as usize;
-            counts[idx] += 1;
+    let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+    state.hard_mode = record.hard_mode;
+
+    let mut rows = Vec::new();
+    for (i, guess) in record.guesses(words)?.into_iter().enumerate() {
+        let before = state.candidates.len();
+        let expected_bits = Suggestion::score(&guess.word, &state, dict).entropy;
+        dict.filter(&mut state.candidates, &guess);
+        let after = state.candidates.len();
+        if after == 0 {
+            return Err(format!("No answer fits row {} ({})", i + 1, guess.word));
         }
+        state.add_attempt(guess.clone());
+        rows.push(RowAnalysis {
+            guess,
+            before,
+            after,
+            expected_bits,
+            actual_bits: (before as f64 / after as f64).log2(),
+        });
     }
+    Ok(rows)
+}
 
-    // Pass 2: yellows where counts remain
-    for i in 0..WORD_LEN {
-        if trits[i] == 0 {
-            let idx = (g[i].to_ascii_uppercase() - b'A') as usize;
-            if counts[idx] > 0 {
-                trits[i] = 1; // yellow
-                counts[idx] -= 1;
-            }
+/// Share of `words` with each letter at each position: (letter, position) -> fraction.
+pub fn letter_frequencies<'a, I: IntoIterator<Item = &'a str>>(
+    words: I,
+) -> HashMap<(char, usize), f64> {
+    let mut freq_map: HashMap<(char, usize), usize> = HashMap::new();
+    let mut total = 0usize;
+
+    for word in words {
+        total += 1;
+        for (i, ch) in word.chars().enumerate() {
+            *freq_map.entry((ch, i)).or_insert(0) += 1;
         }
     }
 
-    // Encode five trits in base-3
-    let mut id = 0usize;
-    for &t in &trits {
-        id = id * 3 + t as usize;
-    }
-    id
+    freq_map
+        .into_iter()
+        .map(|(key, count)| (key, count as f64 / total as f64))
+        .collect()
 }
 
-/// Decode a pattern ID back to a visual string
-pub fn pattern_to_string(pid: usize) -> String {
-    let mut result = String::new();
-    let mut p = pid;
-
-    for _ in 0..5 {
-        let trit = p % 3;
-        result.push(match trit {
-            0 => '⬜',
-            1 => '🟨',
-            2 => '🟩',
-            _ => '?',
-        });
-        p /= 3;
+/// Share of `words` containing each letter at least once.
+pub fn letter_presence<'a, I: IntoIterator<Item = &'a str>>(words: I) -> HashMap<char, f64> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut total = 0usize;
+
+    for word in words {
+        total += 1;
+        let letters: HashSet<char> = word.chars().collect();
+        for ch in letters {
+            *counts.entry(ch).or_insert(0) += 1;
+        }
     }
 
-    result.chars().rev().collect()
+    counts
+        .into_iter()
+        .map(|(ch, count)| (ch, count as f64 / total as f64))
+        .collect()
+}
+
+/// Decode a pattern ID back to a visual string
+pub fn pattern_to_string(pattern: &Pattern) -> String {
+    pattern.to_emoji()
 }
@@ -0,0 +1,111 @@
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::core::calculate_feedback;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::solver::{SolverStrategy, Suggestion};
+use crate::wordle::types::{GameState, Guess};
+
+/// Games that take longer than this are given up as failures.
+pub const MAX_TURNS: usize = 12;
+
+/// How a strategy did over a set of answers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    pub games: usize,
+    /// Guesses used over the solved games.
+    pub total_guesses: usize,
+    /// `histogram[n]` is the number of games solved in exactly `n` guesses.
+    pub histogram: Vec<usize>,
+    /// Answers not found within `MAX_TURNS`.
+    pub failures: Vec<String>,
+}
+
+impl BenchmarkResult {
+    pub fn solved(&self) -> usize {
+        self.games - self.failures.len()
+    }
+
+    /// Average guesses over the solved games.
+    pub fn average(&self) -> f64 {
+        if self.solved() == 0 {
+            return 0.0;
+        }
+        self.total_guesses as f64 / self.solved() as f64
+    }
+
+    /// Most guesses any solved game took.
+    pub fn max_guesses(&self) -> usize {
+        self.histogram.iter().rposition(|&n| n > 0).unwrap_or(0)
+    }
+
+    /// Games solved in at most `turns` guesses.
+    pub fn solved_within(&self, turns: usize) -> usize {
+        self.histogram.iter().take(turns + 1).sum()
+    }
+}
+
+/// Play one game against answer index `answer` and return the guesses made,
+/// each scored against the candidates left when it was played. The last guess
+/// is the answer unless the game ran past `MAX_TURNS`.
+pub fn play(
+    solver: &dyn SolverStrategy,
+    dict: &Dictionary,
+    answer: usize,
+    hard_mode: bool,
+) -> Vec<Suggestion> {
+    let target = dict.word(answer);
+    let mut state = GameState::new(CandidateSet::full(dict.num_answers()), dict.alphabet());
+    state.hard_mode = hard_mode;
+    let mut guesses = Vec::new();
+    while guesses.len() < MAX_TURNS {
+        let Some(suggestion) = solver.suggest(&state, dict, 1).into_iter().next() else {
+            break;
+        };
+        let word = suggestion.word.clone();
+        guesses.push(suggestion);
+        if word == target {
+            break;
+        }
+        let guess = Guess::new(word.clone(), calculate_feedback(&word, target));
+        dict.filter(&mut state.candidates, &guess);
+        state.add_attempt(guess);
+    }
+    guesses
+}
+
+/// Play every answer index in `answers`, reporting progress on stderr if asked.
+pub fn benchmark(
+    solver: &dyn SolverStrategy,
+    dict: &Dictionary,
+    answers: &[usize],
+    hard_mode: bool,
+    progress: bool,
+) -> BenchmarkResult {
+    let mut result = BenchmarkResult {
+        games: 0,
+        total_guesses: 0,
+        histogram: vec![0; MAX_TURNS + 1],
+        failures: Vec::new(),
+    };
+    for (i, &answer) in answers.iter().enumerate() {
+        let guesses = play(solver, dict, answer, hard_mode);
+        result.games += 1;
+        if guesses.last().map(|s| s.word.as_str()) == Some(dict.word(answer)) {
+            result.total_guesses += guesses.len();
+            result.histogram[guesses.len()] += 1;
+        } else {
+            result.failures.push(dict.word(answer).to_string());
+        }
+        if progress && ((i + 1) % 50 == 0 || i + 1 == answers.len()) {
+            eprint!(
+                "\r  {}/{} games, {:.3} average",
+                i + 1,
+                answers.len(),
+                result.average()
+            );
+        }
+    }
+    if progress {
+        eprintln!();
+    }
+    result
+}
@@ -0,0 +1,150 @@
+use crate::wordle::alphabet::normalize_word;
+use crate::wordle::analyzer::StartingWordAnalyzer;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::table::{cache_dir, dictionary_hash};
+use crate::wordle::types::Guess;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io;
+
+/// Openers, by entropy, whose second guesses are worked out when building a book.
+const SHORTLIST: usize = 10;
+
+/// The best opener for a dictionary and the best second guess after each
+/// feedback it can get.
+///
+/// Stored as text, one entry per line:
+///
+/// ```text
+/// opener SALET
+/// BBBBB COURT
+/// BBBBY TRIPE
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningBook {
+    pub opener: String,
+    replies: HashMap<Pattern, String>,
+}
+
+impl OpeningBook {
+    pub fn new(opener: String, replies: impl IntoIterator<Item = (Pattern, String)>) -> Self {
+        OpeningBook {
+            opener,
+            replies: replies.into_iter().collect(),
+        }
+    }
+
+    /// Second guess after the opener gets `feedback`.
+    pub fn reply(&self, feedback: Pattern) -> Option<&str> {
+        self.replies.get(&feedback).map(String::as_str)
+    }
+
+    /// The book's guess for a game that has followed it so far.
+    pub fn lookup(&self, attempts: &[Guess]) -> Option<&str> {
+        match attempts {
+            [] => Some(&self.opener),
+            [first] if first.word == self.opener => self.reply(first.feedback),
+            _ => None,
+        }
+    }
+
+    /// Opener and replies as `StartingWordAnalyzer` picks them for the
+    /// analyzer's dictionary. The openers with the most entropy are shortlisted,
+    /// each gets the entropy solver's reply to every feedback, and the pair that
+    /// leaves the fewest answers after two guesses wins.
+    pub fn build(analyzer: &StartingWordAnalyzer) -> Self {
+        let mut best: Option<(f64, OpeningBook)> = None;
+        for opener in analyzer.find_best_starters(SHORTLIST) {
+            let opener = opener.word;
+            let replies = analyzer.second_guesses(&opener);
+            let remaining = analyzer.expected_remaining_after(&opener, &replies);
+            if best.as_ref().is_none_or(|(b, _)| remaining < *b) {
+                best = Some((remaining, OpeningBook::new(opener, replies)));
+            }
+        }
+        best.map(|(_, book)| book)
+            .unwrap_or_else(|| OpeningBook::new(String::new(), []))
+    }
+
+    /// The cached book for `dict` (its word lists and prior), built and saved on
+    /// first use. `dict` must not carry a book yet, so strategies compute the
+    /// replies rather than read them back.
+    pub fn load_or_build(dict: Dictionary) -> (Dictionary, Self) {
+        let path = cache_dir().join(format!("book-{:016x}.txt", book_hash(&dict)));
+        if let Ok(book) = Self::load(&path.to_string_lossy()) {
+            return (dict, book);
+        }
+
+        let analyzer = StartingWordAnalyzer::from_dictionary(dict);
+        let book = Self::build(&analyzer);
+        if let Err(e) = book.save(&path.to_string_lossy()) {
+            eprintln!("⚠️  Could not write opening book {}: {}", path.display(), e);
+        }
+        (analyzer.into_dictionary(), book)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!("opener {}\n", self.opener);
+        let mut replies: Vec<(&Pattern, &String)> = self.replies.iter().collect();
+        replies.sort_by_key(|(p, _)| p.id());
+        for (pattern, word) in replies {
+            let _ = writeln!(out, "{} {}", pattern, word);
+        }
+        out
+    }
+
+    /// Parse the text form. Blank lines and `#` comments are skipped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut opener = None;
+        let mut replies = HashMap::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, word) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("line {}: expected PATTERN WORD", n + 1))?;
+            let word = normalize_word(word.trim());
+            if key == "opener" {
+                opener = Some(word);
+            } else {
+                let pattern: Pattern = key.parse().map_err(|e| format!("line {}: {}", n + 1, e))?;
+                replies.insert(pattern, word);
+            }
+        }
+        let opener = opener.ok_or("The book has no opener line")?;
+        let word_len = opener.chars().count();
+        if let Some((pattern, _)) = replies
+            .iter()
+            .find(|(p, w)| p.len() != word_len || p.is_all_green() || w.chars().count() != word_len)
+        {
+            return Err(format!("Reply for {} does not fit {}", pattern, opener));
+        }
+        Ok(OpeningBook { opener, replies })
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        if let Some(dir) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_text())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+/// Both word lists and the prior weights, since all of them change the book.
+fn book_hash(dict: &Dictionary) -> u64 {
+    let mut hash = dictionary_hash(dict.guesses(), dict.answers());
+    for s in 0..dict.num_answers() {
+        hash ^= dict.prior().weight(s).to_bits();
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
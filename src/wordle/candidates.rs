@@ -0,0 +1,119 @@
+/// A set of dictionary positions stored as a bitset, one bit per word.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CandidateSet {
+    bits: Vec<u64>,
+    universe: usize,
+}
+
+impl CandidateSet {
+    /// No words out of a dictionary of `universe` words.
+    pub fn empty(universe: usize) -> Self {
+        CandidateSet {
+            bits: vec![0; universe.div_ceil(64)],
+            universe,
+        }
+    }
+
+    /// Every word of a dictionary of `universe` words.
+    pub fn full(universe: usize) -> Self {
+        let mut set = Self::empty(universe);
+        for w in set.bits.iter_mut() {
+            *w = u64::MAX;
+        }
+        let tail = universe % 64;
+        if tail != 0 {
+            if let Some(last) = set.bits.last_mut() {
+                *last = (1u64 << tail) - 1;
+            }
+        }
+        set
+    }
+
+    pub fn from_indices<I: IntoIterator<Item = usize>>(universe: usize, indices: I) -> Self {
+        let mut set = Self::empty(universe);
+        for i in indices {
+            set.insert(i);
+        }
+        set
+    }
+
+    /// Size of the dictionary this set indexes into.
+    pub fn universe(&self) -> usize {
+        self.universe
+    }
+
+    #[inline]
+    pub fn insert(&mut self, i: usize) {
+        self.bits[i / 64] |= 1 << (i % 64);
+    }
+
+    #[inline]
+    pub fn remove(&mut self, i: usize) {
+        self.bits[i / 64] &= !(1 << (i % 64));
+    }
+
+    #[inline]
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.universe && self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Number of words in the set (popcount).
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        for w in self.bits.iter_mut() {
+            *w = 0;
+        }
+    }
+
+    /// Lowest index in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// Indices in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(|(block, &word)| {
+            let mut w = word;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let bit = w.trailing_zeros() as usize;
+                w &= w - 1;
+                Some(block * 64 + bit)
+            })
+        })
+    }
+
+    /// In-place AND.
+    pub fn intersect_with(&mut self, other: &CandidateSet) {
+        debug_assert_eq!(self.universe, other.universe);
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a &= b;
+        }
+    }
+
+    /// In-place OR.
+    pub fn union_with(&mut self, other: &CandidateSet) {
+        debug_assert_eq!(self.universe, other.universe);
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a |= b;
+        }
+    }
+
+    /// `|self & other|` without allocating.
+    pub fn intersection_len(&self, other: &CandidateSet) -> usize {
+        self.bits
+            .iter()
+            .zip(&other.bits)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+}
@@ -1,119 +1,99 @@
+use crate::wordle::alphabet::{normalize_word, word_length};
+use crate::wordle::knowledge::Knowledge;
+use crate::wordle::pattern::Pattern;
 use crate::wordle::types::Guess;
 
-fn get_positions(vec: &Vec<char>, c: char, word: &String) -> Vec<(usize, char)> {
-    vec.iter()
-        .enumerate()
-        .filter_map(|(i, &x)| {
-            if x == c {
-                Some((i, word.chars().nth(i).unwrap()))
-            } else {
-                None
-            }
-        })
-        .collect()
-}
+/// Shortest and longest supported word lengths.
+pub const MIN_WORD_LEN: usize = 4;
+pub const MAX_WORD_LEN: usize = 12;
 
-pub fn filter_by_green(words: &mut Vec<String>, guess: &Guess) {
-    let greens = get_positions(&guess.feedback, 'G', &guess.word);
-    words.retain(|word| {
-        greens
-            .iter()
-            .all(|&(i, c)| word.chars().nth(i).unwrap() == c)
-    });
+/// Keep only words that would have produced `guess.feedback`.
+pub fn filter_words(words: &mut Vec<String>, guess: &Guess) {
+    let knowledge = Knowledge::from_guesses([guess]);
+    words.retain(|word| knowledge.matches(word));
 }
 
-pub fn filter_by_yellow(words: &mut Vec<String>, guess: &Guess) {
-    let yellows = get_positions(&guess.feedback, 'Y', &guess.word);
-    words.retain(|word| {
-        yellows.iter().all(|&(i, c)| {
-            // Word must contain the letter
-            word.contains(c) &&
-            // But NOT at the guessed position
-            word.chars().nth(i).unwrap() != c
-        })
-    });
+/// Return Wordle feedback encoded in base-3 (trits):
+/// 0 = gray, 1 = yellow, 2 = green  => integer in 0..3^L.
+/// Works letter by letter, so any alphabet is fine. For words outside the
+/// dictionary; the pattern table compares dense `Alphabet` indices instead.
+#[inline]
+pub fn feedback_id(guess: &str, solution: &str) -> usize {
+    let mut g = ['\0'; MAX_WORD_LEN];
+    let mut s = ['\0'; MAX_WORD_LEN];
+    let len = fill_letters(&mut g, guess);
+    let solution_len = fill_letters(&mut s, solution);
+    debug_assert_eq!(len, solution_len);
+    if len > MAX_WORD_LEN || len != solution_len {
+        return 0; // defensive; guesses and solutions always share the dictionary length
+    }
+    feedback_letters(&g[..len], &s[..len])
 }
 
-pub fn filter_by_black(words: &mut Vec<String>, guess: &Guess) {
-    let blacks = get_positions(&guess.feedback, 'B', &guess.word);
-
-    // Get unique black letters that are NOT also green or yellow
-    let green_yellow_letters: Vec<char> = guess
-        .feedback
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &fb)| {
-            if fb == 'G' || fb == 'Y' {
-                Some(guess.word.chars().nth(i).unwrap())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    words.retain(|word| {
-        blacks.iter().all(|&(_, c)| {
-            // If this letter also appears as green/yellow, don't filter based on it
-            if green_yellow_letters.contains(&c) {
-                true
-            } else {
-                // Black letter should NOT exist in the word at all
-                !word.contains(c)
+/// Feedback id for two equally long words given as letter slices.
+/// Two-pass algorithm: mark greens (consuming those solution letters), then mark
+/// yellows left to right while unused copies remain.
+#[inline]
+pub fn feedback_letters<T: PartialEq>(g: &[T], s: &[T]) -> usize {
+    let len = g.len();
+    let mut trits = [0u8; MAX_WORD_LEN]; // 0=B, 1=Y, 2=G
+    let mut used = [false; MAX_WORD_LEN]; // solution letters already matched
+
+    // Pass 1: greens
+    for i in 0..len {
+        if g[i] == s[i] {
+            trits[i] = 2; // green
+            used[i] = true;
+        }
+    }
+    // Pass 2: yellows where an unused copy remains
+    for i in 0..len {
+        if trits[i] == 0 {
+            if let Some(j) = (0..len).find(|&j| !used[j] && g[i] == s[j]) {
+                trits[i] = 1; // yellow
+                used[j] = true;
             }
-        })
-    });
-}
-
-pub fn filter_words(words: &mut Vec<String>, guess: &Guess) {
-    filter_by_green(words, guess);
-    filter_by_yellow(words, guess);
-    filter_by_black(words, guess);
-}
-
-pub fn calculate_feedback(guess: &str, solution: &str) -> Vec<char> {
-    let guess_chars: Vec<char> = guess.chars().collect();
-    let solution_chars: Vec<char> = solution.chars().collect();
-    let mut feedback = vec!['B'; 5];
-    let mut solution_used = vec![false; 5];
-
-    // First pass: mark greens
-    for i in 0..5 {
-        if guess_chars[i] == solution_chars[i] {
-            feedback[i] = 'G';
-            solution_used[i] = true;
         }
     }
 
-    // Second pass: mark yellows
-    for i in 0..5 {
-        if feedback[i] == 'B' {
-            for j in 0..5 {
-                if !solution_used[j] && guess_chars[i] == solution_chars[j] {
-                    feedback[i] = 'Y';
-                    solution_used[j] = true;
-                    break;
-                }
-            }
+    // Encode the trits in base-3, first letter most significant
+    trits[..len]
+        .iter()
+        .fold(0usize, |id, &t| id * 3 + t as usize)
+}
+
+/// Copy the letters of `word` into `buf`; returns the letter count (which may
+/// exceed the buffer, in which case the word is too long).
+fn fill_letters(buf: &mut [char; MAX_WORD_LEN], word: &str) -> usize {
+    let mut n = 0;
+    for c in word.chars() {
+        if n < MAX_WORD_LEN {
+            buf[n] = c;
         }
+        n += 1;
     }
+    n
+}
 
-    feedback
+pub fn calculate_feedback(guess: &str, solution: &str) -> Pattern {
+    Pattern::from_id(feedback_id(guess, solution), word_length(guess))
 }
 
-pub fn parse_input(input: &str) -> Result<Guess, String> {
+pub fn parse_input(input: &str, word_len: usize) -> Result<Guess, String> {
     let parts: Vec<&str> = input.split_whitespace().collect();
     if parts.len() != 2 {
         return Err("Input must be WORD FEEDBACK".to_string());
     }
 
-    let word = parts[0].to_string().to_uppercase();
-    let feedback: Vec<char> = parts[1].to_uppercase().chars().collect();
+    let word = normalize_word(parts[0]);
 
-    if word.len() != 5 {
-        return Err("Word must be 5 letters".to_string());
+    if word_length(&word) != word_len {
+        return Err(format!("Word must be {} letters", word_len));
     }
-    if feedback.len() != 5 || !feedback.iter().all(|&c| c == 'G' || c == 'Y' || c == 'B') {
-        return Err("Feedback must be 5 characters of G, Y, or B".to_string());
+    let feedback_error = || format!("Feedback must be {} characters of G, Y, or B", word_len);
+    let feedback: Pattern = parts[1].parse().map_err(|_| feedback_error())?;
+    if feedback.len() != word_len {
+        return Err(feedback_error());
     }
     Ok(Guess::new(word, feedback))
 }
@@ -121,12 +101,15 @@ pub fn parse_input(input: &str) -> Result<Guess, String> {
 // Embed the word list at compile time
 const WORD_LIST: &str = include_str!("../../dict/words.txt");
 
+/// Length of the words in the embedded list.
+pub const EMBEDDED_WORD_LEN: usize = 5;
+
 pub fn load_words() -> Result<Vec<String>, std::io::Error> {
     // Parse the embedded word list
     let words: Vec<String> = WORD_LIST
         .lines()
-        .map(|word| word.trim().to_uppercase())
-        .filter(|word| word.len() == 5)
+        .map(normalize_word)
+        .filter(|word| word_length(word) == EMBEDDED_WORD_LEN)
         .collect();
 
     if words.is_empty() {
@@ -139,9 +122,8 @@ pub fn load_words() -> Result<Vec<String>, std::io::Error> {
     Ok(words)
 }
 
-// Optional: Keep ability to load from file for development
-#[allow(dead_code)]
-pub fn load_words_from_file(path: &str) -> Result<Vec<String>, std::io::Error> {
+/// Load a word list from disk, keeping only words of `word_len` letters.
+pub fn load_words_from_file(path: &str, word_len: usize) -> Result<Vec<String>, std::io::Error> {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
@@ -149,10 +131,17 @@ pub fn load_words_from_file(path: &str) -> Result<Vec<String>, std::io::Error> {
     let reader = BufReader::new(file);
     let words: Vec<String> = reader
         .lines()
-        .filter_map(|line| line.ok())
-        .map(|word| word.to_uppercase())
-        .filter(|word| word.len() == 5)
+        .map_while(Result::ok)
+        .map(|word| normalize_word(&word))
+        .filter(|word| word_length(word) == word_len && word.chars().all(char::is_alphabetic))
         .collect();
 
+    if words.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("No {}-letter words found in {}", word_len, path),
+        ));
+    }
+
     Ok(words)
 }
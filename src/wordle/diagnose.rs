@@ -0,0 +1,226 @@
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::knowledge::Knowledge;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::types::{FeedbackType, Guess};
+
+/// Most tiles a suggested fix may recolour.
+const MAX_EDITS: usize = 2;
+
+/// Up to this many attempts every subset is checked for conflicts; past it a
+/// single minimal conflict is found by deletion.
+const MAX_EXHAUSTIVE: usize = 12;
+
+/// Why no candidates are left, and how the entered feedback could be corrected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnosis {
+    /// Smallest groups of attempts (0-based indices) that no word satisfies together.
+    pub conflicts: Vec<Conflict>,
+    /// Recolourings with the fewest changed tiles that leave at least one candidate.
+    pub fixes: Vec<FeedbackFix>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub attempts: Vec<usize>,
+    /// The feedback contradicts itself (e.g. a letter both gray and required),
+    /// rather than just ruling out every dictionary word.
+    pub impossible: bool,
+}
+
+/// One recoloured tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileEdit {
+    pub attempt: usize,
+    pub position: usize,
+    pub from: FeedbackType,
+    pub to: FeedbackType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackFix {
+    pub edits: Vec<TileEdit>,
+    /// The attempts with the edits applied.
+    pub attempts: Vec<Guess>,
+    /// Candidates left after the fix.
+    pub candidates: CandidateSet,
+}
+
+/// Diagnose a set of attempts that leaves no candidates.
+pub fn diagnose(attempts: &[Guess], dict: &Dictionary) -> Diagnosis {
+    Diagnosis {
+        conflicts: find_conflicts(attempts, dict),
+        fixes: find_fixes(attempts, dict),
+    }
+}
+
+/// Candidates consistent with every attempt.
+pub fn candidates_for(attempts: &[Guess], dict: &Dictionary) -> CandidateSet {
+    let mut candidates = CandidateSet::full(dict.num_answers());
+    for guess in attempts {
+        dict.filter(&mut candidates, guess);
+    }
+    candidates
+}
+
+/// Minimal conflicting subsets, smallest first: no proper subset of a reported
+/// group is itself a conflict. With more than `MAX_EXHAUSTIVE` attempts only one
+/// is reported.
+fn find_conflicts(attempts: &[Guess], dict: &Dictionary) -> Vec<Conflict> {
+    let single: Vec<CandidateSet> = attempts
+        .iter()
+        .map(|g| candidates_for(std::slice::from_ref(g), dict))
+        .collect();
+    let groups = if attempts.len() <= MAX_EXHAUSTIVE {
+        all_minimal_conflicts(&single, dict)
+    } else {
+        minimal_conflict(&single, dict).into_iter().collect()
+    };
+
+    groups
+        .into_iter()
+        .map(|members| {
+            let knowledge = Knowledge::from_guesses(members.iter().map(|&i| &attempts[i]));
+            Conflict {
+                attempts: members,
+                impossible: knowledge.is_contradictory(),
+            }
+        })
+        .collect()
+}
+
+/// Whether no word fits every attempt in `members`.
+fn conflicts(
+    single: &[CandidateSet],
+    members: impl Iterator<Item = usize>,
+    dict: &Dictionary,
+) -> bool {
+    let mut set = CandidateSet::full(dict.num_answers());
+    for i in members {
+        set.intersect_with(&single[i]);
+    }
+    set.is_empty()
+}
+
+/// Every minimal conflict, by checking subsets smallest first.
+fn all_minimal_conflicts(single: &[CandidateSet], dict: &Dictionary) -> Vec<Vec<usize>> {
+    let n = single.len();
+    let mut masks: Vec<u32> = (1..1u32 << n).collect();
+    masks.sort_by_key(|m| (m.count_ones(), *m));
+
+    let members = |mask: u32| (0..n).filter(move |i| mask & (1 << i) != 0);
+    let mut found: Vec<u32> = Vec::new();
+    for mask in masks {
+        if found.iter().any(|&f| f & !mask == 0) {
+            continue; // contains a smaller conflict
+        }
+        if conflicts(single, members(mask), dict) {
+            found.push(mask);
+        }
+    }
+    found
+        .into_iter()
+        .map(|mask| members(mask).collect())
+        .collect()
+}
+
+/// One minimal conflict: drop each attempt in turn unless the rest would then
+/// fit some word. None when the attempts do not conflict at all.
+fn minimal_conflict(single: &[CandidateSet], dict: &Dictionary) -> Option<Vec<usize>> {
+    let mut members: Vec<usize> = (0..single.len()).collect();
+    if !conflicts(single, members.iter().copied(), dict) {
+        return None;
+    }
+    let mut i = 0;
+    while i < members.len() {
+        let without = members.iter().copied().filter(|&m| m != members[i]);
+        if conflicts(single, without, dict) {
+            members.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Some(members)
+}
+
+/// Every recolouring with the fewest edited tiles (up to `MAX_EDITS`) that leaves
+/// candidates. Later attempts are listed first, since typos tend to be recent.
+fn find_fixes(attempts: &[Guess], dict: &Dictionary) -> Vec<FeedbackFix> {
+    let mut single_edits: Vec<TileEdit> = Vec::new();
+    for (a, guess) in attempts.iter().enumerate().rev() {
+        for (position, from) in guess.feedback.tiles().enumerate() {
+            for to in [
+                FeedbackType::Green,
+                FeedbackType::Yellow,
+                FeedbackType::Black,
+            ] {
+                if to != from {
+                    single_edits.push(TileEdit {
+                        attempt: a,
+                        position,
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+    }
+
+    for size in 1..=MAX_EDITS {
+        let mut fixes = Vec::new();
+        for edits in combinations(&single_edits, size) {
+            // Two edits of the same tile are not a smaller fix.
+            let same_tile = edits.iter().enumerate().any(|(i, a)| {
+                edits[i + 1..]
+                    .iter()
+                    .any(|b| (a.attempt, a.position) == (b.attempt, b.position))
+            });
+            if same_tile {
+                continue;
+            }
+            let fixed = apply_edits(attempts, &edits);
+            let candidates = candidates_for(&fixed, dict);
+            if !candidates.is_empty() {
+                fixes.push(FeedbackFix {
+                    edits,
+                    attempts: fixed,
+                    candidates,
+                });
+            }
+        }
+        if !fixes.is_empty() {
+            return fixes;
+        }
+    }
+    Vec::new()
+}
+
+fn apply_edits(attempts: &[Guess], edits: &[TileEdit]) -> Vec<Guess> {
+    let mut fixed = attempts.to_vec();
+    for edit in edits {
+        let guess = &mut fixed[edit.attempt];
+        let tiles: Vec<FeedbackType> = guess
+            .feedback
+            .tiles()
+            .enumerate()
+            .map(|(i, t)| if i == edit.position { edit.to } else { t })
+            .collect();
+        guess.feedback = Pattern::from_tiles(tiles);
+    }
+    fixed
+}
+
+/// All `k`-element combinations of `items`, preserving order.
+fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut out = Vec::new();
+    for (i, &item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], k - 1) {
+            rest.insert(0, item);
+            out.push(rest);
+        }
+    }
+    out
+}
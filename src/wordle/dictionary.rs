@@ -0,0 +1,236 @@
+use crate::wordle::alphabet::Alphabet;
+use crate::wordle::book::OpeningBook;
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::core::{feedback_id, load_words};
+use crate::wordle::prior::Prior;
+use crate::wordle::table::PatternTable;
+use crate::wordle::types::Guess;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Candidate masks for one guess: pattern id -> words that produce it.
+pub type GuessMasks = HashMap<usize, CandidateSet>;
+
+/// The active word lists together with their precomputed pattern table.
+///
+/// `answers` are the words the puzzle can pick; `guesses` are all words a player
+/// may enter. Answers come first in the guess list, so a word below
+/// `num_answers()` has the same index in both, and solvers can refer to any word
+/// by its guess index while candidate sets index the answers.
+pub struct Dictionary {
+    guesses: Vec<String>,
+    num_answers: usize,
+    word_len: usize,
+    alphabet: Alphabet,
+    index: HashMap<String, usize>,
+    /// Guess x answer patterns.
+    table: PatternTable,
+    /// Built on first use per guess; filtering is then a single AND.
+    masks: Vec<OnceLock<GuessMasks>>,
+    /// How likely each answer is; uniform unless a frequency list was given.
+    prior: Prior,
+    /// Precomputed first and second guesses, consulted by every strategy.
+    book: Option<OpeningBook>,
+}
+
+impl Dictionary {
+    /// One list used as both answers and guesses; the pattern table is built in
+    /// memory (no cache file).
+    pub fn new(words: Vec<String>) -> Self {
+        Self::with_guesses(words, Vec::new())
+    }
+
+    /// Separate answer and guess lists, built in memory. Answers are always
+    /// allowed as guesses, so `guesses` may or may not repeat them.
+    pub fn with_guesses(answers: Vec<String>, guesses: Vec<String>) -> Self {
+        let (guesses, num_answers) = merge_lists(answers, guesses);
+        let table = PatternTable::build(&guesses, &guesses[..num_answers]);
+        Self::with_table(guesses, num_answers, table)
+    }
+
+    /// Like `with_guesses`, using the on-disk pattern cache (built on first use).
+    pub fn cached(answers: Vec<String>, guesses: Vec<String>) -> Self {
+        let (guesses, num_answers) = merge_lists(answers, guesses);
+        let table = PatternTable::load_or_build(&guesses, &guesses[..num_answers]);
+        Self::with_table(guesses, num_answers, table)
+    }
+
+    /// The embedded answer list with a cached pattern table. No separate guess
+    /// list is embedded, so the answers are the only allowed guesses.
+    pub fn embedded() -> Result<Self, std::io::Error> {
+        Ok(Self::cached(load_words()?, Vec::new()))
+    }
+
+    /// Weight the answers by `prior` when scoring guesses.
+    pub fn with_prior(mut self, prior: Prior) -> Self {
+        self.prior = prior;
+        self
+    }
+
+    /// Open with `book` instead of computing the first two guesses.
+    pub fn with_book(mut self, book: OpeningBook) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    fn with_table(guesses: Vec<String>, num_answers: usize, table: PatternTable) -> Self {
+        let index = guesses
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (w.clone(), i))
+            .collect();
+        let masks = (0..guesses.len()).map(|_| OnceLock::new()).collect();
+        Dictionary {
+            word_len: table.word_len(),
+            alphabet: Alphabet::from_words(&guesses),
+            guesses,
+            num_answers,
+            index,
+            table,
+            masks,
+            prior: Prior::uniform(num_answers),
+            book: None,
+        }
+    }
+
+    /// Words that can be the answer.
+    pub fn answers(&self) -> &[String] {
+        &self.guesses[..self.num_answers]
+    }
+
+    /// Every allowed guess, answers first.
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+
+    /// Word at guess index `i` (which is also its answer index when `i < num_answers()`).
+    pub fn word(&self, i: usize) -> &str {
+        &self.guesses[i]
+    }
+
+    pub fn num_answers(&self) -> usize {
+        self.num_answers
+    }
+
+    pub fn num_guesses(&self) -> usize {
+        self.guesses.len()
+    }
+
+    /// Letters the words are spelled with.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// Letters per word.
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    /// True when there are no answers.
+    pub fn is_empty(&self) -> bool {
+        self.num_answers == 0
+    }
+
+    /// Guess index of a word.
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.index.get(word).copied()
+    }
+
+    /// Answer index of a word, if it can be the answer.
+    pub fn answer_index(&self, word: &str) -> Option<usize> {
+        self.index_of(word).filter(|&i| i < self.num_answers)
+    }
+
+    pub fn prior(&self) -> &Prior {
+        &self.prior
+    }
+
+    pub fn book(&self) -> Option<&OpeningBook> {
+        self.book.as_ref()
+    }
+
+    pub fn table(&self) -> &PatternTable {
+        &self.table
+    }
+
+    /// Pattern ids of `guess` against every answer, from the table when the guess
+    /// is in the dictionary and computed on the fly otherwise.
+    pub fn pattern_row(&self, guess: &str) -> Vec<usize> {
+        match self.index_of(guess) {
+            Some(g) => self.table.row(g).collect(),
+            None => self
+                .answers()
+                .iter()
+                .map(|s| feedback_id(guess, s))
+                .collect(),
+        }
+    }
+
+    /// Every answer in `set`, in dictionary order.
+    pub fn words_in(&self, set: &CandidateSet) -> Vec<&str> {
+        set.iter().map(|i| self.word(i)).collect()
+    }
+
+    /// Per-pattern candidate masks for a guess (guess index).
+    pub fn masks(&self, guess: usize) -> &GuessMasks {
+        self.masks[guess].get_or_init(|| {
+            let mut masks = GuessMasks::new();
+            for (s, pid) in self.table.row(guess).enumerate() {
+                masks
+                    .entry(pid)
+                    .or_insert_with(|| CandidateSet::empty(self.num_answers))
+                    .insert(s);
+            }
+            masks
+        })
+    }
+
+    /// Answers that would give feedback `pid` to `guess`.
+    pub fn mask(&self, guess: usize, pid: usize) -> Option<&CandidateSet> {
+        self.masks(guess).get(&pid)
+    }
+
+    /// Keep only candidates consistent with `guess` and its feedback.
+    pub fn filter(&self, candidates: &mut CandidateSet, guess: &Guess) {
+        let pid = guess.feedback.id();
+        match self.index_of(&guess.word) {
+            Some(g) => match self.mask(g, pid) {
+                Some(mask) => candidates.intersect_with(mask),
+                None => candidates.clear(),
+            },
+            // Guess outside the dictionary: no precomputed row, compare directly.
+            None => {
+                let rejected: Vec<usize> = candidates
+                    .iter()
+                    .filter(|&s| feedback_id(&guess.word, self.word(s)) != pid)
+                    .collect();
+                for s in rejected {
+                    candidates.remove(s);
+                }
+            }
+        }
+    }
+
+    /// Bucket sizes (pattern id, count) of `guess` over `candidates`, computed as
+    /// popcounts of each mask ANDed with the candidate set.
+    pub fn bucket_sizes(&self, candidates: &CandidateSet, guess: usize) -> Vec<(usize, usize)> {
+        self.masks(guess)
+            .iter()
+            .map(|(&pid, mask)| (pid, mask.intersection_len(candidates)))
+            .filter(|&(_, n)| n > 0)
+            .collect()
+    }
+}
+
+/// Answers followed by the guesses that are not answers, without duplicates;
+/// returns the merged list and the number of answers.
+fn merge_lists(answers: Vec<String>, guesses: Vec<String>) -> (Vec<String>, usize) {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut merged: Vec<String> = answers
+        .into_iter()
+        .filter(|w| seen.insert(w.clone()))
+        .collect();
+    let num_answers = merged.len();
+    merged.extend(guesses.into_iter().filter(|w| seen.insert(w.clone())));
+    (merged, num_answers)
+}
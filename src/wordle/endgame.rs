@@ -0,0 +1,287 @@
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::num_patterns;
+use crate::wordle::solver::ProbePolicy;
+use std::collections::{HashMap, HashSet};
+
+/// Candidate sets up to this size are solved exactly.
+pub const ENDGAME_MAX: usize = 30;
+
+/// Slack for comparing expected guesses, which are sums of probabilities.
+const EPS: f64 = 1e-9;
+
+/// Best play from a candidate set: the guess, the expected guesses to finish
+/// (counting it) and the most guesses it can take.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndgamePlan {
+    pub guess: usize,
+    pub expected_guesses: f64,
+    pub max_guesses: usize,
+}
+
+impl EndgamePlan {
+    /// Fewer expected guesses, then fewer in the worst case.
+    fn better_than(&self, other: &EndgamePlan) -> bool {
+        self.expected_guesses < other.expected_guesses - EPS
+            || (self.expected_guesses <= other.expected_guesses + EPS
+                && self.max_guesses < other.max_guesses)
+    }
+}
+
+/// Exact expected-guess minimiser for small candidate sets, by depth-first
+/// branch-and-bound over every allowed guess. Answers are weighted by the
+/// dictionary's prior and subproblems are memoized on their candidate list.
+///
+/// In hard mode only candidates are tried: they always satisfy the hints, so
+/// every line of play stays legal. Otherwise the non-candidates tried are the
+/// ones `probes` picks, which is all of them unless set with `with_probes`.
+pub struct Endgame<'a> {
+    dict: &'a Dictionary,
+    hard_mode: bool,
+    probes: ProbePolicy,
+    memo: HashMap<Vec<usize>, Solved>,
+}
+
+/// What is known about a subproblem.
+#[derive(Debug, Clone, Copy)]
+enum Solved {
+    Exact(EndgamePlan),
+    /// No plan expects fewer guesses than this.
+    AtLeast(f64),
+}
+
+/// One feedback's candidates, their share of the probability and the share
+/// of the likeliest of them.
+struct Bucket {
+    cands: Vec<usize>,
+    mass: f64,
+    top: f64,
+}
+
+impl Bucket {
+    /// Least the bucket adds to the expected guesses: its answers need at least
+    /// one more guess, and all but one of them at least two.
+    fn lower_bound(&self) -> f64 {
+        if self.cands.len() == 1 {
+            self.mass
+        } else {
+            2.0 * self.mass - self.top
+        }
+    }
+}
+
+impl<'a> Endgame<'a> {
+    pub fn new(dict: &'a Dictionary, hard_mode: bool) -> Self {
+        Endgame {
+            dict,
+            hard_mode,
+            probes: ProbePolicy::full(),
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Try only the probes `probes` picks at each position. The plans are then
+    /// best among those probes rather than exact.
+    pub fn with_probes(mut self, probes: ProbePolicy) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Best plan for `candidates`, or None when there are none.
+    pub fn solve(&mut self, candidates: &CandidateSet) -> Option<EndgamePlan> {
+        let cands: Vec<usize> = candidates.iter().collect();
+        self.search(&cands, f64::INFINITY)
+    }
+
+    /// The `limit` best first guesses for `candidates`, each with its best
+    /// continuation, best first. Guesses that split the candidates exactly like
+    /// a better one are left out.
+    pub fn rank(&mut self, candidates: &CandidateSet, limit: usize) -> Vec<EndgamePlan> {
+        let cands: Vec<usize> = candidates.iter().collect();
+        if limit == 0 || cands.is_empty() {
+            return Vec::new();
+        }
+        if cands.len() <= 2 {
+            let probs = self.dict.prior().probabilities(&cands);
+            let mut plans: Vec<EndgamePlan> = cands
+                .iter()
+                .zip(&probs)
+                .map(|(&g, &p)| EndgamePlan {
+                    guess: g,
+                    expected_guesses: 2.0 - p,
+                    max_guesses: cands.len(),
+                })
+                .collect();
+            plans.sort_by(|a, b| a.expected_guesses.total_cmp(&b.expected_guesses));
+            plans.truncate(limit);
+            return plans;
+        }
+
+        let probs = self.dict.prior().probabilities(&cands);
+        let mut ranked: Vec<EndgamePlan> = Vec::new();
+        for g in self.ordered_guesses(&cands) {
+            let budget = match ranked.get(limit - 1) {
+                Some(plan) => plan.expected_guesses,
+                None => f64::INFINITY,
+            };
+            if let Some(plan) = self.evaluate(g, &cands, &probs, budget) {
+                let at = ranked.partition_point(|p| !plan.better_than(p));
+                ranked.insert(at, plan);
+                ranked.truncate(limit);
+            }
+        }
+        ranked
+    }
+
+    /// Best plan for `cands` expecting at most `budget` guesses.
+    fn search(&mut self, cands: &[usize], budget: f64) -> Option<EndgamePlan> {
+        let probs = self.dict.prior().probabilities(cands);
+        match cands.len() {
+            0 => return None,
+            1 => {
+                let plan = EndgamePlan {
+                    guess: cands[0],
+                    expected_guesses: 1.0,
+                    max_guesses: 1,
+                };
+                return (1.0 <= budget + EPS).then_some(plan);
+            }
+            2 => {
+                // Guess the likelier word; the other takes one more.
+                let i = usize::from(probs[1] > probs[0]);
+                let plan = EndgamePlan {
+                    guess: cands[i],
+                    expected_guesses: 2.0 - probs[i],
+                    max_guesses: 2,
+                };
+                return (plan.expected_guesses <= budget + EPS).then_some(plan);
+            }
+            _ => {}
+        }
+
+        match self.memo.get(cands) {
+            Some(&Solved::Exact(plan)) => {
+                return (plan.expected_guesses <= budget + EPS).then_some(plan)
+            }
+            Some(&Solved::AtLeast(bound)) if budget < bound - EPS => return None,
+            _ => {}
+        }
+
+        let mut best: Option<EndgamePlan> = None;
+        for g in self.ordered_guesses(cands) {
+            let bound = best.map_or(budget, |b| b.expected_guesses);
+            if let Some(plan) = self.evaluate(g, cands, &probs, bound) {
+                if best.is_none_or(|b| plan.better_than(&b)) {
+                    best = Some(plan);
+                }
+            }
+        }
+
+        let solved = match best {
+            Some(plan) => Solved::Exact(plan),
+            None => Solved::AtLeast(budget),
+        };
+        self.memo.insert(cands.to_vec(), solved);
+        best
+    }
+
+    /// Plan opening with `g`, if it expects at most `budget` guesses.
+    fn evaluate(
+        &mut self,
+        g: usize,
+        cands: &[usize],
+        probs: &[f64],
+        budget: f64,
+    ) -> Option<EndgamePlan> {
+        let mut buckets = self.partition(g, cands, probs);
+        let mut remaining: f64 = buckets.iter().map(Bucket::lower_bound).sum();
+        if 1.0 + remaining > budget + EPS {
+            return None;
+        }
+        // Likeliest buckets first, so hopeless guesses are cut early.
+        buckets.sort_by(|a, b| b.mass.total_cmp(&a.mass));
+
+        let mut plan = EndgamePlan {
+            guess: g,
+            expected_guesses: 1.0,
+            max_guesses: 1,
+        };
+        for bucket in &buckets {
+            remaining -= bucket.lower_bound();
+            let limit = (budget - plan.expected_guesses - remaining) / bucket.mass;
+            let rest = self.search(&bucket.cands, limit)?;
+            plan.expected_guesses += bucket.mass * rest.expected_guesses;
+            plan.max_guesses = plan.max_guesses.max(1 + rest.max_guesses);
+        }
+        Some(plan)
+    }
+
+    /// Candidates split by the feedback `g` gives them, without the answer `g`
+    /// itself.
+    fn partition(&self, g: usize, cands: &[usize], probs: &[f64]) -> Vec<Bucket> {
+        let table = self.dict.table();
+        let mut buckets: HashMap<usize, Bucket> = HashMap::new();
+        for (&s, &p) in cands.iter().zip(probs) {
+            if s == g {
+                continue;
+            }
+            let bucket = buckets.entry(table.get(g, s)).or_insert(Bucket {
+                cands: Vec::new(),
+                mass: 0.0,
+                top: 0.0,
+            });
+            bucket.cands.push(s);
+            bucket.mass += p;
+            bucket.top = bucket.top.max(p);
+        }
+        buckets.into_values().collect()
+    }
+
+    /// Guesses worth trying for `cands`, most promising first by sum of squared
+    /// bucket sizes. Of guesses that split the candidates the same way only the
+    /// first is kept.
+    fn ordered_guesses(&self, cands: &[usize]) -> Vec<usize> {
+        let table = self.dict.table();
+        let n = cands.len();
+        let mut pool = cands.to_vec();
+        if !self.hard_mode {
+            let others: Vec<usize> = (0..self.dict.num_guesses())
+                .filter(|g| cands.binary_search(g).is_err())
+                .collect();
+            pool.extend(self.probes.select(others, cands, self.dict));
+        }
+
+        let mut labels = vec![u16::MAX; num_patterns(self.dict.word_len())];
+        let mut seen: HashSet<(bool, Vec<u16>)> = HashSet::new();
+        let mut scored: Vec<(usize, bool, usize)> = Vec::new();
+        for g in pool {
+            let is_candidate = cands.binary_search(&g).is_ok();
+            // Number the buckets in order of first appearance, so guesses
+            // that group the candidates alike get the same key.
+            let mut split = Vec::with_capacity(n);
+            let mut sizes: Vec<usize> = Vec::new();
+            for &s in cands {
+                let pid = table.get(g, s);
+                if labels[pid] == u16::MAX {
+                    labels[pid] = sizes.len() as u16;
+                    sizes.push(0);
+                }
+                sizes[labels[pid] as usize] += 1;
+                split.push(labels[pid]);
+            }
+            for &s in cands {
+                labels[table.get(g, s)] = u16::MAX;
+            }
+            // A probe that splits nothing cannot help.
+            if sizes.len() == 1 && !is_candidate {
+                continue;
+            }
+            if seen.insert((is_candidate, split)) {
+                let sum_sq = sizes.iter().map(|&c| c * c).sum();
+                scored.push((sum_sq, !is_candidate, g));
+            }
+        }
+        scored.sort_unstable();
+        scored.into_iter().map(|(_, _, g)| g).collect()
+    }
+}
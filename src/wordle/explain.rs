@@ -0,0 +1,80 @@
+use crate::wordle::core::feedback_id;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::solver::Suggestion;
+use crate::wordle::types::GameState;
+use std::collections::HashMap;
+
+/// How one guess splits the current candidates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub suggestion: Suggestion,
+    /// Candidates left after the worst feedback.
+    pub worst_case: usize,
+    /// Feedback groups, largest first.
+    pub buckets: Vec<Bucket>,
+}
+
+/// The candidates that would give one feedback.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    pub pattern: Pattern,
+    pub size: usize,
+    /// Up to the requested number of the group's words, likeliest first.
+    pub examples: Vec<String>,
+}
+
+/// Split the candidates of `state` by the feedback `word` would get, keeping
+/// `examples` words per group.
+pub fn explain(word: &str, state: &GameState, dict: &Dictionary, examples: usize) -> Explanation {
+    let index = dict.index_of(word);
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for s in state.candidates.iter() {
+        let pid = match index {
+            Some(g) => dict.table().get(g, s),
+            None => feedback_id(word, dict.word(s)),
+        };
+        groups.entry(pid).or_default().push(s);
+    }
+
+    let mut buckets: Vec<Bucket> = groups
+        .into_iter()
+        .map(|(pid, mut members)| {
+            members.sort_by(|&a, &b| dict.prior().weight(b).total_cmp(&dict.prior().weight(a)));
+            Bucket {
+                pattern: Pattern::from_id(pid, dict.word_len()),
+                size: members.len(),
+                examples: members
+                    .iter()
+                    .take(examples)
+                    .map(|&s| dict.word(s).to_string())
+                    .collect(),
+            }
+        })
+        .collect();
+    buckets.sort_by_key(|b| (std::cmp::Reverse(b.size), b.pattern.id()));
+
+    Explanation {
+        suggestion: Suggestion::score(word, state, dict),
+        worst_case: buckets.first().map_or(0, |b| b.size),
+        buckets,
+    }
+}
+
+/// The candidate that splits the others best (most bits, then fewest expected
+/// left), to compare a probe against.
+pub fn best_candidate(state: &GameState, dict: &Dictionary) -> Option<Suggestion> {
+    let cand_ids: Vec<usize> = state.candidates.iter().collect();
+    cand_ids
+        .iter()
+        .map(|&g| Suggestion::new(g, &cand_ids, dict, true))
+        .reduce(|best, s| {
+            let better = s.entropy > best.entropy
+                || (s.entropy == best.entropy && s.expected_remaining < best.expected_remaining);
+            if better {
+                s
+            } else {
+                best
+            }
+        })
+}
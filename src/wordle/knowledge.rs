@@ -0,0 +1,207 @@
+use crate::wordle::types::{FeedbackType, Guess};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Everything the feedback so far says about the answer.
+///
+/// For every letter it tracks how many copies the answer holds (at least `min`,
+/// at most `max`), and for every position which letters are still allowed there.
+/// A word matches exactly when each guess would have produced its recorded feedback
+/// against it, so duplicate letters ("exactly one E") are handled too.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Knowledge {
+    /// Word length, fixed by the first guess.
+    len: Option<usize>,
+    /// Green letter per position.
+    fixed: Vec<Option<char>>,
+    /// Letters known not to be at each position (yellow or gray there).
+    forbidden: Vec<BTreeSet<char>>,
+    /// Per letter: (min copies, max copies if capped).
+    counts: BTreeMap<char, (usize, Option<usize>)>,
+}
+
+impl Knowledge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_guesses<'a, I: IntoIterator<Item = &'a Guess>>(guesses: I) -> Self {
+        let mut knowledge = Self::new();
+        for guess in guesses {
+            knowledge.add(guess);
+        }
+        knowledge
+    }
+
+    /// Fold one guess and its feedback into the constraints.
+    pub fn add(&mut self, guess: &Guess) {
+        let letters: Vec<char> = guess.word.chars().collect();
+        let len = letters.len();
+        if self.len.is_none() {
+            self.len = Some(len);
+            self.fixed = vec![None; len];
+            self.forbidden = vec![BTreeSet::new(); len];
+        }
+
+        // Per letter in this guess: (green + yellow tiles, any gray tile?)
+        let mut seen: BTreeMap<char, (usize, bool)> = BTreeMap::new();
+        for (i, (tile, c)) in guess.feedback.tiles().zip(letters).enumerate() {
+            if i >= self.fixed.len() {
+                break;
+            }
+            let entry = seen.entry(c).or_insert((0, false));
+            match tile {
+                FeedbackType::Green => {
+                    entry.0 += 1;
+                    if self.fixed[i].is_some_and(|f| f != c) {
+                        // Two different greens on one square: nothing can match.
+                        self.forbidden[i].insert(c);
+                    }
+                    self.fixed[i] = Some(c);
+                }
+                FeedbackType::Yellow => {
+                    entry.0 += 1;
+                    self.forbidden[i].insert(c);
+                }
+                FeedbackType::Black => {
+                    entry.1 = true;
+                    self.forbidden[i].insert(c);
+                }
+            }
+        }
+
+        for (c, (present, capped)) in seen {
+            let (min, max) = self.counts.entry(c).or_insert((0, None));
+            *min = (*min).max(present);
+            if capped {
+                *max = Some(max.map_or(present, |m| m.min(present)));
+            }
+        }
+    }
+
+    /// Would every recorded guess have produced its feedback against `word`?
+    pub fn matches(&self, word: &str) -> bool {
+        let letters: Vec<char> = word.chars().collect();
+        if let Some(len) = self.len {
+            if letters.len() != len {
+                return false;
+            }
+        }
+
+        for (i, &c) in letters.iter().enumerate() {
+            if !self.allows(i, c) {
+                return false;
+            }
+        }
+
+        self.counts.iter().all(|(&c, &(min, max))| {
+            let n = letters.iter().filter(|&&l| l == c).count();
+            n >= min && max.is_none_or(|m| n <= m)
+        })
+    }
+
+    /// Can `c` still be at position `i`?
+    pub fn allows(&self, i: usize, c: char) -> bool {
+        if i >= self.fixed.len() {
+            return self.len.is_none();
+        }
+        self.fixed[i].is_none_or(|f| f == c) && !self.forbidden[i].contains(&c)
+    }
+
+    /// Does `word` reuse every revealed hint, as hard mode demands? Greens must stay
+    /// in place and yellow letters must appear somewhere; grays may be reused.
+    pub fn keeps_hints(&self, word: &str) -> bool {
+        self.hard_mode_violation(word).is_none()
+    }
+
+    /// The first hard-mode rule `word` breaks, worded like the official game:
+    /// "2nd letter must be R", then "Guess must contain E".
+    pub fn hard_mode_violation(&self, word: &str) -> Option<String> {
+        let letters: Vec<char> = word.chars().collect();
+        for (i, f) in self.fixed.iter().enumerate() {
+            if let Some(c) = *f {
+                if letters.get(i) != Some(&c) {
+                    return Some(format!("{} letter must be {}", ordinal(i + 1), c));
+                }
+            }
+        }
+        for (c, min) in self.required_letters() {
+            if letters.iter().filter(|&&l| l == c).count() < min {
+                return Some(match min {
+                    1 => format!("Guess must contain {}", c),
+                    2 => format!("Guess must contain {} twice", c),
+                    n => format!("Guess must contain {} {} times", c, n),
+                });
+            }
+        }
+        None
+    }
+
+    /// Green letter at position `i`, if known.
+    pub fn fixed(&self, i: usize) -> Option<char> {
+        self.fixed.get(i).copied().flatten()
+    }
+
+    /// Fewest copies of `c` the answer can hold.
+    pub fn min_count(&self, c: char) -> usize {
+        self.counts.get(&c).map_or(0, |&(min, _)| min)
+    }
+
+    /// Most copies of `c` the answer can hold, if that is known.
+    pub fn max_count(&self, c: char) -> Option<usize> {
+        self.counts.get(&c).and_then(|&(_, max)| max)
+    }
+
+    /// Letters the answer is known to contain, with their minimum counts.
+    pub fn required_letters(&self) -> Vec<(char, usize)> {
+        self.counts
+            .iter()
+            .filter(|(_, &(min, _))| min > 0)
+            .map(|(&c, &(min, _))| (c, min))
+            .collect()
+    }
+
+    /// Letters ruled out entirely (max count of zero).
+    pub fn excluded_letters(&self) -> Vec<char> {
+        self.counts
+            .iter()
+            .filter(|(_, &(_, max))| max == Some(0))
+            .map(|(&c, _)| c)
+            .collect()
+    }
+
+    /// True when no word at all can satisfy the constraints.
+    pub fn is_contradictory(&self) -> bool {
+        let Some(len) = self.len else {
+            return false;
+        };
+        // Greens from different guesses can demand more copies than any one guess saw.
+        let min_of = |c: char, min: usize| {
+            let greens = self.fixed.iter().filter(|&&f| f == Some(c)).count();
+            min.max(greens)
+        };
+        let count_clash = self
+            .counts
+            .iter()
+            .any(|(&c, &(min, max))| max.is_some_and(|m| min_of(c, min) > m));
+        let position_clash =
+            (0..len).any(|i| self.fixed[i].is_some_and(|f| self.forbidden[i].contains(&f)));
+        let required: usize = self
+            .counts
+            .iter()
+            .map(|(&c, &(min, _))| min_of(c, min))
+            .sum();
+        count_clash || position_clash || required > len
+    }
+}
+
+/// "1st", "2nd", "3rd", "4th", ... for letter positions.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
@@ -1,4 +1,22 @@
+pub mod absurdle;
+pub mod adversary;
+pub mod alphabet;
 pub mod analyzer;
+pub mod benchmark;
+pub mod book;
+pub mod candidates;
 pub mod core;
+pub mod diagnose;
+pub mod dictionary;
+pub mod endgame;
+pub mod explain;
+pub mod knowledge;
+pub mod pattern;
+pub mod prior;
+pub mod reverse;
+pub mod rng;
+pub mod share;
 pub mod solver;
+pub mod table;
+pub mod tree;
 pub mod types;
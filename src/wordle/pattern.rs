@@ -0,0 +1,133 @@
+use crate::wordle::core::MAX_WORD_LEN;
+use crate::wordle::types::FeedbackType;
+use std::fmt;
+use std::str::FromStr;
+
+/// Feedback for one guess, stored as its base-3 id:
+/// 0 = gray, 1 = yellow, 2 = green, first tile is the most significant trit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    id: usize,
+    len: usize,
+}
+
+/// How a pattern is rendered as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternStyle {
+    /// `GYBBG`
+    Letters,
+    /// `🟩🟨⬜⬜🟩`
+    Emoji,
+    /// `21002`
+    Trits,
+}
+
+/// Number of distinct patterns for words of `len` letters (3^len).
+pub fn num_patterns(len: usize) -> usize {
+    3usize.pow(len as u32)
+}
+
+impl Pattern {
+    pub fn from_id(id: usize, len: usize) -> Self {
+        debug_assert!(id < num_patterns(len));
+        Pattern { id, len }
+    }
+
+    /// Pattern of up to `MAX_WORD_LEN` tiles; longer ones do not fit an id.
+    pub fn from_tiles<I: IntoIterator<Item = FeedbackType>>(tiles: I) -> Self {
+        let mut id = 0usize;
+        let mut len = 0usize;
+        for tile in tiles {
+            assert!(
+                len < MAX_WORD_LEN,
+                "feedback longer than {} tiles",
+                MAX_WORD_LEN
+            );
+            id = id * 3 + tile.trit();
+            len += 1;
+        }
+        Pattern { id, len }
+    }
+
+    pub fn all_green(len: usize) -> Self {
+        Pattern {
+            id: num_patterns(len) - 1,
+            len,
+        }
+    }
+
+    pub fn id(self) -> usize {
+        self.id
+    }
+
+    pub fn len(self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_all_green(self) -> bool {
+        self.id == num_patterns(self.len) - 1
+    }
+
+    /// Tile at position `i` (0-based, left to right).
+    pub fn tile(self, i: usize) -> FeedbackType {
+        let shift = 3usize.pow((self.len - 1 - i) as u32);
+        FeedbackType::from_trit(self.id / shift % 3)
+    }
+
+    /// Tiles from left to right.
+    pub fn tiles(self) -> impl Iterator<Item = FeedbackType> {
+        (0..self.len).map(move |i| self.tile(i))
+    }
+
+    pub fn render(self, style: PatternStyle) -> String {
+        self.tiles()
+            .map(|t| match style {
+                PatternStyle::Letters => t.letter(),
+                PatternStyle::Emoji => t.emoji(),
+                PatternStyle::Trits => char::from_digit(t.trit() as u32, 3).unwrap(),
+            })
+            .collect()
+    }
+
+    pub fn to_emoji(self) -> String {
+        self.render(PatternStyle::Emoji)
+    }
+}
+
+/// Letters by default (`GYBBG`); the alternate flag (`{:#}`) prints emoji tiles.
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&self.render(PatternStyle::Emoji))
+        } else {
+            f.write_str(&self.render(PatternStyle::Letters))
+        }
+    }
+}
+
+/// Parses letters (`GYBBG`, any case), emoji tiles (`🟩🟨⬜⬛`, or high-contrast
+/// `🟧🟦`) or trits (`21002`). Emoji variation selectors are skipped.
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = s
+            .trim()
+            .chars()
+            .filter(|&c| c != '\u{fe0f}')
+            .map(|c| FeedbackType::from_char(c).ok_or(c))
+            .collect::<Result<Vec<_>, char>>()
+            .map_err(|c| format!("Invalid feedback tile '{}'", c))?;
+        if tiles.is_empty() {
+            return Err("Feedback is empty".to_string());
+        }
+        if tiles.len() > MAX_WORD_LEN {
+            return Err(format!("Feedback is longer than {} tiles", MAX_WORD_LEN));
+        }
+        Ok(Pattern::from_tiles(tiles))
+    }
+}
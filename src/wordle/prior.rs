@@ -0,0 +1,122 @@
+use crate::wordle::alphabet::normalize_word;
+use std::collections::HashMap;
+use std::io;
+
+/// Roughly how many of the most frequent words count as common. The sigmoid is
+/// centred here, or halfway down shorter answer lists.
+const COMMON_WORDS: f64 = 3000.0;
+
+/// The sigmoid runs from about 1 to about 0 over twice the centre rank.
+const SIGMOID_STEEPNESS: f64 = 10.0;
+
+/// How likely each answer is to be picked, as a relative weight per answer index.
+///
+/// Solvers normalize the weights over the current candidates, so
+/// π(s) = w(s) / Σ w over the candidates (ALGO.md §6).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prior {
+    weights: Vec<f64>,
+    uniform: bool,
+}
+
+impl Prior {
+    /// Every answer equally likely.
+    pub fn uniform(num_answers: usize) -> Self {
+        Prior {
+            weights: vec![1.0; num_answers],
+            uniform: true,
+        }
+    }
+
+    /// Weight answers by a sigmoid over their frequency rank, so common words are
+    /// all likely and rare ones fade out rather than being scored by raw counts.
+    /// Words missing from `counts` rank after every answer.
+    pub fn from_counts(answers: &[String], counts: &HashMap<String, f64>) -> Self {
+        let n = answers.len();
+        let center = COMMON_WORDS.min(n as f64 / 2.0);
+        let width = (center / SIGMOID_STEEPNESS).max(1.0);
+
+        let mut order: Vec<(usize, f64)> = answers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, w)| counts.get(w).map(|&c| (i, c)))
+            .collect();
+        order.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut weights = vec![sigmoid((center - n as f64) / width); n];
+        let mut rank = 0;
+        for (i, &(a, c)) in order.iter().enumerate() {
+            // Equal counts share a rank.
+            if i > 0 && c < order[i - 1].1 {
+                rank = i;
+            }
+            weights[a] = sigmoid((center - rank as f64) / width);
+        }
+        Prior {
+            weights,
+            uniform: false,
+        }
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.uniform
+    }
+
+    /// Unnormalized weight of an answer.
+    #[inline]
+    pub fn weight(&self, answer: usize) -> f64 {
+        self.weights[answer]
+    }
+
+    /// Probability of each of `candidates` being the answer, in the same order.
+    pub fn probabilities(&self, candidates: &[usize]) -> Vec<f64> {
+        let total: f64 = candidates.iter().map(|&s| self.weight(s)).sum();
+        candidates
+            .iter()
+            .map(|&s| {
+                if total > 0.0 {
+                    self.weight(s) / total
+                } else {
+                    1.0 / candidates.len() as f64
+                }
+            })
+            .collect()
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Read a frequency file: one word and its count per line, separated by
+/// whitespace, a comma or a tab. Blank lines and `#` comments are skipped.
+pub fn load_frequencies(path: &str) -> io::Result<HashMap<String, f64>> {
+    let text = std::fs::read_to_string(path)?;
+    let mut counts = HashMap::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|f| !f.is_empty());
+        let (word, count) = match (fields.next(), fields.next()) {
+            (Some(word), Some(count)) => (word, count),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected WORD COUNT", n + 1),
+                ))
+            }
+        };
+        let count: f64 = count.parse().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: invalid count '{}'", n + 1, count),
+            )
+        })?;
+        counts.insert(normalize_word(word), count);
+    }
+    Ok(counts)
+}
@@ -0,0 +1,141 @@
+use crate::wordle::alphabet::normalize_word;
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::knowledge::Knowledge;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::solver::score_guess;
+use crate::wordle::types::Guess;
+use std::cmp::Ordering;
+
+/// Partial sequences kept after each row of the beam search.
+const BEAM_WIDTH: usize = 256;
+
+/// A full guess sequence that reproduces a shared grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessSequence {
+    pub words: Vec<String>,
+    /// Every guess reused the hints revealed before it.
+    pub hard_mode: bool,
+    /// Expected information of each guess at the time it was played, summed.
+    pub bits: f64,
+}
+
+/// Split a shared grid into rows. Rows are separated by newlines or `/`, and
+/// each must hold `word_len` tiles in any style `Pattern` parses.
+pub fn parse_grid(grid: &str, word_len: usize) -> Result<Vec<Pattern>, String> {
+    let rows: Vec<Pattern> = grid
+        .split(['/', '\n'])
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .enumerate()
+        .map(|(i, row)| {
+            let pattern: Pattern = row.parse().map_err(|e| format!("Row {}: {}", i + 1, e))?;
+            if pattern.len() != word_len {
+                return Err(format!("Row {}: expected {} tiles", i + 1, word_len));
+            }
+            Ok(pattern)
+        })
+        .collect::<Result<_, _>>()?;
+    if rows.is_empty() {
+        return Err("The grid has no rows".to_string());
+    }
+    Ok(rows)
+}
+
+/// Answer index of `answer`, which every guess is scored against.
+pub fn answer_index(answer: &str, dict: &Dictionary) -> Result<usize, String> {
+    let answer = normalize_word(answer);
+    dict.answer_index(&answer)
+        .ok_or_else(|| format!("{} is not in the answer list", answer))
+}
+
+/// Allowed guesses that give `row` against the answer.
+pub fn row_words(row: Pattern, answer: usize, dict: &Dictionary) -> Vec<usize> {
+    let table = dict.table();
+    (0..dict.num_guesses())
+        .filter(|&g| table.get(g, answer) == row.id())
+        .collect()
+}
+
+/// The most plausible guess sequences behind `rows`, best first.
+///
+/// Sequences where every guess keeps the hints seen so far (hard-mode play) rank
+/// ahead of the rest; ties are broken by the entropy the guesses carried. The
+/// search keeps the best `BEAM_WIDTH` prefixes per row, so it is not exhaustive.
+pub fn rank_sequences(
+    rows: &[Pattern],
+    answer: usize,
+    dict: &Dictionary,
+    limit: usize,
+) -> Vec<GuessSequence> {
+    let mut beam = vec![Prefix {
+        words: Vec::new(),
+        knowledge: Knowledge::new(),
+        candidates: CandidateSet::full(dict.num_answers()),
+        hard_mode: true,
+        bits: 0.0,
+    }];
+
+    for &row in rows {
+        let options = row_words(row, answer, dict);
+        let mut next = Vec::new();
+        for prefix in &beam {
+            let cand_ids: Vec<usize> = prefix.candidates.iter().collect();
+            for &g in &options {
+                if prefix.words.contains(&g) {
+                    continue;
+                }
+                let (entropy, _, _) = score_guess(g, &cand_ids, dict);
+                let guess = Guess::new(dict.word(g).to_string(), row);
+                let mut candidates = prefix.candidates.clone();
+                dict.filter(&mut candidates, &guess);
+                let mut knowledge = prefix.knowledge.clone();
+                let hard_mode = prefix.hard_mode && knowledge.keeps_hints(&guess.word);
+                knowledge.add(&guess);
+
+                let mut words = prefix.words.clone();
+                words.push(g);
+                next.push(Prefix {
+                    words,
+                    knowledge,
+                    candidates,
+                    hard_mode,
+                    bits: prefix.bits + entropy,
+                });
+            }
+        }
+        next.sort_by(Prefix::rank);
+        next.truncate(BEAM_WIDTH);
+        beam = next;
+    }
+
+    beam.into_iter()
+        .take(limit)
+        .map(|prefix| GuessSequence {
+            words: prefix
+                .words
+                .iter()
+                .map(|&g| dict.word(g).to_string())
+                .collect(),
+            hard_mode: prefix.hard_mode,
+            bits: prefix.bits,
+        })
+        .collect()
+}
+
+/// A partial sequence in the beam.
+struct Prefix {
+    words: Vec<usize>,
+    knowledge: Knowledge,
+    candidates: CandidateSet,
+    hard_mode: bool,
+    bits: f64,
+}
+
+impl Prefix {
+    fn rank(a: &Prefix, b: &Prefix) -> Ordering {
+        b.hard_mode
+            .cmp(&a.hard_mode)
+            .then(b.bits.partial_cmp(&a.bits).unwrap_or(Ordering::Equal))
+    }
+}
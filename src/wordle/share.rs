@@ -0,0 +1,166 @@
+use crate::wordle::alphabet::normalize_word;
+use crate::wordle::pattern::Pattern;
+use crate::wordle::types::{FeedbackType, Guess};
+
+/// One game as posted with the Share button:
+///
+/// ```text
+/// Wordle 1,234 4/6*
+///
+/// ⬜🟨⬜⬜⬜
+/// ⬜🟨🟨⬜🟨
+/// ⬜🟩🟩🟩🟩
+/// 🟩🟩🟩🟩🟩
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareRecord {
+    /// Name before the puzzle number, e.g. "Wordle".
+    pub game: String,
+    pub puzzle: u32,
+    /// The last row is all green.
+    pub solved: bool,
+    /// Rows allowed, the number after the slash.
+    pub max_attempts: usize,
+    /// The `*` after the score.
+    pub hard_mode: bool,
+    pub rows: Vec<Pattern>,
+}
+
+impl ShareRecord {
+    /// Number of guesses played.
+    pub fn attempts(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Pair each row with the word that was played, ready for `GameState::add_attempt`.
+    pub fn guesses(&self, words: &[&str]) -> Result<Vec<Guess>, String> {
+        if words.len() != self.rows.len() {
+            return Err(format!(
+                "Expected {} words, one per row, got {}",
+                self.rows.len(),
+                words.len()
+            ));
+        }
+        self.rows
+            .iter()
+            .zip(words)
+            .map(|(&row, word)| {
+                let word = normalize_word(word);
+                if word.chars().count() != row.len() {
+                    return Err(format!("{} does not fit a {}-tile row", word, row.len()));
+                }
+                Ok(Guess::new(word, row))
+            })
+            .collect()
+    }
+}
+
+/// Does `text` start with a share header such as "Wordle 1,234 4/6"?
+pub fn is_share_text(text: &str) -> bool {
+    text.lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| parse_header(line).is_ok())
+}
+
+/// Parse a share block. Text after the grid (links, comments) is ignored.
+pub fn parse_share(text: &str) -> Result<ShareRecord, String> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let header = lines.next().ok_or("Share text is empty")?;
+    let (game, puzzle, score, max_attempts, hard_mode) = parse_header(header)?;
+
+    let rows: Vec<Pattern> = lines
+        .take_while(|line| is_grid_row(line))
+        .map(|line| line.parse())
+        .collect::<Result<_, _>>()?;
+    if rows.is_empty() {
+        return Err("Share text has no emoji rows".to_string());
+    }
+    let word_len = rows[0].len();
+    if let Some(i) = rows.iter().position(|row| row.len() != word_len) {
+        return Err(format!(
+            "Row {} has {} tiles, expected {}",
+            i + 1,
+            rows[i].len(),
+            word_len
+        ));
+    }
+    if rows.len() > max_attempts {
+        return Err(format!(
+            "{} rows but only {} attempts allowed",
+            rows.len(),
+            max_attempts
+        ));
+    }
+
+    let solved = rows.last().is_some_and(|row| row.is_all_green());
+    match score {
+        Some(n) if !solved || n != rows.len() => {
+            return Err(format!(
+                "Score {}/{} does not match the grid",
+                n, max_attempts
+            ));
+        }
+        None if solved || rows.len() != max_attempts => {
+            return Err(format!("Score X/{} does not match the grid", max_attempts));
+        }
+        _ => {}
+    }
+
+    Ok(ShareRecord {
+        game,
+        puzzle,
+        solved,
+        max_attempts,
+        hard_mode,
+        rows,
+    })
+}
+
+/// Header fields: (game, puzzle, score or None for X, max attempts, hard mode).
+type Header = (String, u32, Option<usize>, usize, bool);
+
+fn parse_header(line: &str) -> Result<Header, String> {
+    let invalid = || format!("Not a share header: {}", line.trim());
+    let (rest, score) = line
+        .trim()
+        .rsplit_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+
+    let (score, hard_mode) = match score.strip_suffix('*') {
+        Some(score) => (score, true),
+        None => (score, false),
+    };
+    let (solved_in, max_attempts) = score.split_once('/').ok_or_else(invalid)?;
+    let max_attempts: usize = max_attempts.parse().map_err(|_| invalid())?;
+    let score = match solved_in {
+        "X" | "x" => None,
+        n => Some(n.parse::<usize>().map_err(|_| invalid())?),
+    };
+    if max_attempts == 0 || score.is_some_and(|n| n == 0 || n > max_attempts) {
+        return Err(invalid());
+    }
+
+    // The puzzle number may carry thousands separators: "1,234", "1.234", "1 234", "#1234".
+    let start = rest
+        .find(|c: char| c.is_ascii_digit() || c == '#')
+        .ok_or_else(invalid)?;
+    let game = rest[..start].trim().to_string();
+    let number = &rest[start..];
+    if game.is_empty()
+        || !number
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '#' | ',' | '.' | ' '))
+    {
+        return Err(invalid());
+    }
+    let digits: String = number.chars().filter(char::is_ascii_digit).collect();
+    let puzzle: u32 = digits.parse().map_err(|_| invalid())?;
+
+    Ok((game, puzzle, score, max_attempts, hard_mode))
+}
+
+/// A line made only of emoji tiles.
+fn is_grid_row(line: &str) -> bool {
+    line.chars()
+        .all(|c| c == '\u{fe0f}' || (!c.is_ascii() && FeedbackType::from_char(c).is_some()))
+}
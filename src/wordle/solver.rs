@@ -1,251 +1,1306 @@
-use crate::wordle::types::GameState;
-use std::collections::HashSet;
+use crate::wordle::absurdle::AbsurdleSearch;
+use crate::wordle::analyzer::{letter_frequencies, letter_presence};
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::core::{calculate_feedback, feedback_id};
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::endgame::{Endgame, ENDGAME_MAX};
+use crate::wordle::pattern::{num_patterns, Pattern};
+use crate::wordle::rng::Rng;
+use crate::wordle::tree::DecisionTree;
+use crate::wordle::types::{GameState, Guess};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 pub trait SolverStrategy {
-    fn suggest_guess(&self, state: &GameState, all_words: &[String]) -> String;
+    /// Up to `limit` guesses, best first, scored against the candidates.
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion>;
+
+    /// Chance of finding the answer within the game's guesses by playing
+    /// `guess` next, for strategies that play for it.
+    fn solve_probability(
+        &self,
+        _state: &GameState,
+        _dict: &Dictionary,
+        _guess: &str,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// The best guess, or an empty string when there is none.
+    fn suggest_guess(&self, state: &GameState, dict: &Dictionary) -> String {
+        self.suggest(state, dict, 1)
+            .into_iter()
+            .next()
+            .map(|s| s.word)
+            .unwrap_or_default()
+    }
+}
+
+/// A ranked guess and how it splits the current candidates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// Expected information in bits.
+    pub entropy: f64,
+    /// Expected number of candidates left after the guess.
+    pub expected_remaining: f64,
+    /// Chance the guess is the answer.
+    pub p_win: f64,
+    /// The guess could be the answer.
+    pub is_candidate: bool,
 }
 
 pub struct SimpleSolver;
-pub struct EntropyMaximizer;
-pub struct FrequencyAnalyzer;
+
+/// Scores every candidate and a sample of probe words by expected information,
+/// and plays small candidate sets out exactly.
+#[derive(Debug, Clone, Copy)]
+pub struct EntropyMaximizer {
+    /// Which non-candidate probes are scored.
+    pub probes: ProbePolicy,
+    /// Candidate sets up to this size go to the `Endgame` solver, which tries
+    /// the same probes; 0 keeps the entropy scan throughout.
+    pub endgame: usize,
+}
+
+/// How the entropy scan picks the non-candidate probe words it scores.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ProbeSampling {
+    /// Every allowed probe: exact, but slow on large guess lists.
+    Full,
+    /// A random sample; the same seed gives the same sample for the same state.
+    Random { seed: u64 },
+    /// The probes whose letters split the candidates most evenly, ranked by
+    /// the frequency analyzer's probe scoring over every allowed word.
+    #[default]
+    Coverage,
+}
+
+/// Which probes the entropy scan scores, and how many.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProbePolicy {
+    pub sampling: ProbeSampling,
+    /// Probes scored per suggestion; None scales with the candidate count.
+    /// Ignored by `ProbeSampling::Full`.
+    pub budget: Option<usize>,
+}
+
+/// Looks ahead over the `top_k` best entropy guesses and picks the one with the
+/// fewest expected guesses to finish, following each feedback bucket with its own
+/// best guess for `depth` plies.
+pub struct LookaheadSolver {
+    pub top_k: usize,
+    pub depth: usize,
+    /// Report progress on stderr while the top guesses are evaluated.
+    pub progress: bool,
+    /// Probes scored at the root, and how deeper plies sample theirs.
+    pub probes: ProbePolicy,
+}
+
+/// Maximizes the chance of finding the answer within `turns` guesses rather
+/// than the expected number of guesses. Each feedback is followed by its own
+/// best guess; with two turns left every allowed guess is scored exactly, so the
+/// last turns go to guesses that split the likeliest answers apart.
+pub struct SurvivalSolver {
+    /// Guesses allowed in a game.
+    pub turns: usize,
+    /// Guesses tried per position with three or more turns left: this many
+    /// by entropy, and this many by smallest worst-case bucket.
+    pub top_k: usize,
+    /// Probes scored for the root shortlist, and how deeper plies sample theirs.
+    pub probes: ProbePolicy,
+}
+
+/// Monte Carlo tree search: samples hidden answers from the candidates, plays
+/// each of a shortlist of guesses against them and finishes the game with a
+/// cheap rollout policy, then picks the guess with the fewest guesses on
+/// average. Scales to guess lists too large for exhaustive entropy scoring.
+pub struct MctsSolver {
+    /// Rollouts per suggestion; `usize::MAX` leaves the time limit alone to
+    /// stop the search.
+    pub iterations: usize,
+    /// Stop early once this much time has passed.
+    pub time_limit: Option<Duration>,
+    /// Guesses considered at the root, best first by letter coverage.
+    pub actions: usize,
+    /// UCB1 exploration weight, in guesses.
+    pub exploration: f64,
+    pub seed: u64,
+    /// Plays out the rest of each sampled game.
+    pub rollout: Box<dyn SolverStrategy>,
+}
+
+/// Plays a precomputed `DecisionTree`, falling back to `EntropyMaximizer` once
+/// the game leaves the tree.
+pub struct TreeSolver {
+    pub tree: DecisionTree,
+    /// Probes the fallback scores.
+    pub probes: ProbePolicy,
+}
+
+/// Minimizes the largest feedback bucket, bounding the worst case instead of
+/// optimizing the average.
+pub struct MinimaxSolver;
+
+/// Plays against an Absurdle-style host, which keeps the largest feedback
+/// bucket: picks the guesses that corner it in the fewest guesses for sure.
+pub struct AbsurdleSolver;
+
+/// Scores words by how common their letters are among the candidates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrequencyAnalyzer {
+    /// Also consider non-candidate words that split the candidates' letters
+    /// better, rather than only guessing words that could win.
+    pub probes: bool,
+}
+
+impl Suggestion {
+    /// Score guess index `g` against `cand_ids`.
+    pub fn new(g: usize, cand_ids: &[usize], dict: &Dictionary, is_candidate: bool) -> Self {
+        Self::from_score(g, &GuessScore::new(g, cand_ids, dict, is_candidate), dict)
+    }
+
+    /// Score any word, in the dictionary or not, against the candidates of `state`.
+    pub fn score(word: &str, state: &GameState, dict: &Dictionary) -> Self {
+        let cand_ids: Vec<usize> = state.candidates.iter().collect();
+        let (entropy, expected_remaining, p_win) = match dict.index_of(word) {
+            Some(g) => score_guess(g, &cand_ids, dict),
+            None => score_against(
+                cand_ids
+                    .iter()
+                    .map(|&s| (s, feedback_id(word, dict.word(s)))),
+                dict,
+            ),
+        };
+        Suggestion {
+            word: word.to_string(),
+            entropy,
+            expected_remaining,
+            p_win,
+            is_candidate: dict
+                .answer_index(word)
+                .is_some_and(|s| state.candidates.contains(s)),
+        }
+    }
+
+    fn from_score(g: usize, score: &GuessScore, dict: &Dictionary) -> Self {
+        Suggestion {
+            word: dict.word(g).to_string(),
+            entropy: score.entropy,
+            expected_remaining: score.expected_remaining,
+            p_win: score.p_win,
+            is_candidate: score.is_candidate,
+        }
+    }
+}
+
+/// Candidates in dictionary order, scored.
+fn candidates_in_order(cand_ids: &[usize], dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+    cand_ids
+        .iter()
+        .take(limit)
+        .map(|&g| Suggestion::new(g, cand_ids, dict, true))
+        .collect()
+}
 
 impl SolverStrategy for SimpleSolver {
-    fn suggest_guess(&self, state: &GameState, _all_words: &[String]) -> String {
-        if state.attempt_count == 0 {
-            return "CRANE".to_string();
-        }
-        if state.candidates.len() == 1 {
-            return state.candidates[0].clone();
-        }
-        if state.candidates.len() <= 20 {
-            return state
-                .candidates
-                .first()
-                .cloned()
-                .unwrap_or_else(|| "SLATE".to_string());
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            candidates_in_order(&cand_ids, dict, limit)
+        })
+    }
+}
+
+/// The dictionary's opening-book guess while the game still follows the book,
+/// unless hard mode rules it out.
+fn book_guess(state: &GameState, dict: &Dictionary) -> Option<String> {
+    dict.book()?
+        .lookup(&state.attempts)
+        .filter(|&word| state.allows_guess(word))
+        .map(str::to_string)
+}
+
+/// `rank`'s suggestions, with the opening-book guess first while the game
+/// follows the book. The rest is only ranked when more than one is asked for.
+fn book_first(
+    state: &GameState,
+    dict: &Dictionary,
+    limit: usize,
+    rank: impl FnOnce() -> Vec<Suggestion>,
+) -> Vec<Suggestion> {
+    let Some(word) = book_guess(state, dict) else {
+        return rank();
+    };
+    let mut ranked = vec![Suggestion::score(&word, state, dict)];
+    if limit > 1 {
+        ranked.extend(rank().into_iter().filter(|s| s.word != word));
+    }
+    ranked.truncate(limit);
+    ranked
+}
+
+impl Default for EntropyMaximizer {
+    fn default() -> Self {
+        Self::new(ProbePolicy::default())
+    }
+}
+
+impl EntropyMaximizer {
+    pub fn new(probes: ProbePolicy) -> Self {
+        EntropyMaximizer {
+            probes,
+            endgame: ENDGAME_MAX,
+        }
+    }
+
+    pub fn with_endgame(mut self, max_candidates: usize) -> Self {
+        self.endgame = max_candidates;
+        self
+    }
+}
+
+impl SolverStrategy for EntropyMaximizer {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            if cand_ids.len() <= self.endgame {
+                return Endgame::new(dict, state.hard_mode)
+                    .with_probes(self.probes)
+                    .rank(&state.candidates, limit)
+                    .iter()
+                    .map(|plan| {
+                        let g = plan.guess;
+                        Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g))
+                    })
+                    .collect();
+            }
+            if cand_ids.len() <= 2 {
+                return candidates_in_order(&cand_ids, dict, limit);
+            }
+
+            let mut pool = scored_pool(&state.candidates, &cand_ids, dict, &self.probes, |g| {
+                state.allows_guess(dict.word(g))
+            });
+            rank_pool(&mut pool);
+            pool.iter()
+                .take(limit)
+                .map(|(g, score)| Suggestion::from_score(*g, score, dict))
+                .collect()
+        })
+    }
+}
+
+/// Best `GuessScore` first; equal scores keep their order.
+fn rank_pool(pool: &mut [(usize, GuessScore)]) {
+    pool.sort_by(|(_, a), (_, b)| {
+        if a.better_than(b) {
+            std::cmp::Ordering::Less
+        } else if b.better_than(a) {
+            std::cmp::Ordering::Greater
         } else {
-            match state.attempt_count {
-                1 => "SLATE".to_string(),
-                2 => "MOIST".to_string(),
-                _ => state
-                    .candidates
-                    .first()
-                    .cloned()
-                    .unwrap_or_else(|| "PRINT".to_string()),
+            std::cmp::Ordering::Equal
+        }
+    });
+}
+
+/// Default budget for *non-candidate* "probe" guesses (improves splits early).
+/// We'll examine all candidates + ~N sampled non-candidates.
+fn probe_budget(num_candidates: usize) -> usize {
+    match num_candidates {
+        n if n > 500 => 200,
+        n if n > 100 => 300,
+        n if n > 20 => 400,
+        _ => 600,
+    }
+}
+
+impl ProbePolicy {
+    pub fn full() -> Self {
+        ProbePolicy {
+            sampling: ProbeSampling::Full,
+            budget: None,
+        }
+    }
+
+    pub fn random(seed: u64) -> Self {
+        ProbePolicy {
+            sampling: ProbeSampling::Random { seed },
+            budget: None,
+        }
+    }
+
+    pub fn coverage() -> Self {
+        ProbePolicy {
+            sampling: ProbeSampling::Coverage,
+            budget: None,
+        }
+    }
+
+    pub fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// The probes to score out of every allowed non-candidate.
+    pub(crate) fn select(
+        &self,
+        mut probes: Vec<usize>,
+        cand_ids: &[usize],
+        dict: &Dictionary,
+    ) -> Vec<usize> {
+        let budget = self.budget.unwrap_or_else(|| probe_budget(cand_ids.len()));
+        if budget >= probes.len() || self.sampling == ProbeSampling::Full {
+            return probes;
+        }
+        match self.sampling {
+            ProbeSampling::Full => probes,
+            ProbeSampling::Random { seed } => {
+                // Partial Fisher-Yates: the first `budget` slots are the sample.
+                let mut rng = Rng::new(seed);
+                for i in 0..budget {
+                    let j = i + rng.below(probes.len() - i);
+                    probes.swap(i, j);
+                }
+                probes.truncate(budget);
+                probes
+            }
+            ProbeSampling::Coverage => {
+                let score = FrequencyAnalyzer::with_probes().letter_scorer(cand_ids, dict);
+                let mut scored: Vec<(f64, usize)> =
+                    probes.iter().map(|&g| (score(dict.word(g)), g)).collect();
+                if budget > 0 {
+                    scored.select_nth_unstable_by(budget - 1, |a, b| {
+                        b.0.total_cmp(&a.0).then(a.1.cmp(&b.1))
+                    });
+                }
+                scored.truncate(budget);
+                scored.into_iter().map(|(_, g)| g).collect()
             }
         }
     }
 }
 
-impl SolverStrategy for EntropyMaximizer {
-    fn suggest_guess(&self, state: &GameState, all_words: &[String]) -> String {
-        // Base cases
-        if state.attempt_count == 0 {
-            return "SALET".to_string(); // strong opener; adjust to your list
+/// Entropy scores of every candidate, then of the non-candidates that `allows`
+/// (the hard-mode check) accepts and `probes` picks.
+fn scored_pool(
+    candidates: &CandidateSet,
+    cand_ids: &[usize],
+    dict: &Dictionary,
+    probes: &ProbePolicy,
+    allows: impl Fn(usize) -> bool,
+) -> Vec<(usize, GuessScore)> {
+    // --- Pass 1: score all candidates (ensures solvability-now choices are seen)
+    let mut pool: Vec<(usize, GuessScore)> = cand_ids
+        .iter()
+        .map(|&g| (g, GuessScore::new(g, cand_ids, dict, true)))
+        .collect();
+
+    // --- Pass 2: score the probes the policy picks from the allowed-guess list
+    let others: Vec<usize> = (0..dict.num_guesses())
+        .filter(|&g| !candidates.contains(g)) // candidates were pass 1
+        .filter(|&g| allows(g))
+        .collect();
+    for g in probes.select(others, cand_ids, dict) {
+        pool.push((g, GuessScore::new(g, cand_ids, dict, false)));
+    }
+    pool
+}
+
+/// Score lost per extra copy of a letter in a word.
+const REPEAT_PENALTY: f64 = 0.5;
+
+/// Bonus for probe-mode guesses that could also be the answer.
+const CANDIDATE_BONUS: f64 = 0.1;
+
+impl FrequencyAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also consider non-candidate probe words.
+    pub fn with_probes() -> Self {
+        FrequencyAnalyzer { probes: true }
+    }
+}
+
+impl FrequencyAnalyzer {
+    /// Scores words by the letters of `cand_ids`.
+    fn letter_scorer(&self, cand_ids: &[usize], dict: &Dictionary) -> impl Fn(&str) -> f64 {
+        let candidates = cand_ids.iter().map(|&s| dict.word(s));
+        let positional = letter_frequencies(candidates.clone());
+        let presence = letter_presence(candidates);
+
+        // Candidates score by how common their letters are. Probes score by how
+        // evenly their letters split the candidates: a letter every candidate has
+        // (or none has) tells nothing, one half of them have tells the most.
+        let probes = self.probes;
+        let value = move |f: f64| if probes { f.min(1.0 - f) } else { f };
+        move |word: &str| {
+            let letters: Vec<char> = word.chars().collect();
+            let mut seen: Vec<char> = Vec::with_capacity(letters.len());
+            let mut total = 0.0;
+            for (i, &c) in letters.iter().enumerate() {
+                total += value(positional.get(&(c, i)).copied().unwrap_or(0.0));
+                if seen.contains(&c) {
+                    total -= REPEAT_PENALTY;
+                } else {
+                    seen.push(c);
+                    total += value(presence.get(&c).copied().unwrap_or(0.0));
+                }
+            }
+            total
         }
-        if state.candidates.len() == 1 {
-            return state.candidates[0].clone();
+    }
+
+    /// Score of every guess this analyzer considers, in dictionary order.
+    fn scores(&self, state: &GameState, dict: &Dictionary) -> Vec<(usize, f64)> {
+        let cand_ids: Vec<usize> = state.candidates.iter().collect();
+        let score = self.letter_scorer(&cand_ids, dict);
+        if self.probes {
+            (0..dict.num_guesses())
+                .filter_map(|g| {
+                    if state.candidates.contains(g) {
+                        Some((g, score(dict.word(g)) + CANDIDATE_BONUS))
+                    } else if state.allows_guess(dict.word(g)) {
+                        Some((g, score(dict.word(g))))
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        } else {
+            cand_ids.iter().map(|&g| (g, score(dict.word(g)))).collect()
         }
-        if state.candidates.len() <= 2 {
-            return state.candidates[0].clone();
+    }
+}
+
+impl SolverStrategy for FrequencyAnalyzer {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            if cand_ids.len() <= 2 {
+                return candidates_in_order(&cand_ids, dict, limit);
+            }
+
+            // The first of equally scored words wins.
+            let mut scores = self.scores(state, dict);
+            scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scores
+                .iter()
+                .take(limit)
+                .map(|&(g, _)| Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g)))
+                .collect()
+        })
+    }
+}
+
+/// Probe budget below the first ply, where the pool is rebuilt per bucket.
+const NESTED_PROBE_BUDGET: usize = 100;
+
+/// Bits of information a guess is assumed to gain past the lookahead horizon.
+const LEAF_BITS_PER_GUESS: f64 = 3.5;
+
+impl LookaheadSolver {
+    pub const DEFAULT_TOP_K: usize = 10;
+    pub const DEFAULT_DEPTH: usize = 2;
+
+    pub fn new(top_k: usize, depth: usize) -> Self {
+        LookaheadSolver {
+            top_k: top_k.max(1),
+            depth: depth.max(1),
+            progress: false,
+            probes: ProbePolicy::default(),
         }
+    }
 
-        // Always consider every candidate (ensures solvability-now choices are seen).
-        let cand_set: HashSet<&str> = state.candidates.iter().map(|s| s.as_str()).collect();
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn with_probes(mut self, probes: ProbePolicy) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Expected number of guesses to finish, the winning one included, when
+    /// playing the suggested guess from `state`.
+    pub fn expected_guesses(&self, state: &GameState, dict: &Dictionary) -> f64 {
+        self.ranked_guesses(&state.candidates, self.depth, state, dict, true)
+            .first()
+            .map_or(0.0, |&(_, cost)| cost)
+    }
+
+    /// Guesses for `candidates` by expected guesses to finish, fewest first.
+    fn ranked_guesses(
+        &self,
+        candidates: &CandidateSet,
+        depth: usize,
+        state: &GameState,
+        dict: &Dictionary,
+        root: bool,
+    ) -> Vec<(usize, f64)> {
+        let cand_ids: Vec<usize> = candidates.iter().collect();
+        let probs = dict.prior().probabilities(&cand_ids);
+        if cand_ids.len() <= 2 {
+            // Guess the likelier word; the other (if any) takes one more.
+            let mut ranked: Vec<(usize, f64)> = cand_ids
+                .iter()
+                .zip(&probs)
+                .map(|(&s, &p)| (s, 2.0 - p))
+                .collect();
+            ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+            return ranked;
+        }
 
-        // Sampling budget for *non-candidate* "probe" guesses (improves splits early).
-        // We'll examine all candidates + ~N sampled non-candidates.
-        let non_cand_sample_budget = match state.candidates.len() {
-            n if n > 500 => 200,
-            n if n > 100 => 300,
-            n if n > 20 => 400,
-            _ => 600,
+        // Probes are only legal at the root in hard mode, where the state's
+        // hints are known; deeper plies then stick to candidates.
+        let probes = match (root, state.hard_mode) {
+            (true, _) => self.probes,
+            (false, false) => self.probes.with_budget(NESTED_PROBE_BUDGET),
+            (false, true) => self.probes.with_budget(0),
         };
+        let mut pool = scored_pool(candidates, &cand_ids, dict, &probes, |g| {
+            !root || state.allows_guess(dict.word(g))
+        });
+        rank_pool(&mut pool);
+        pool.truncate(self.top_k);
 
-        // --- Pass 1: score all candidates
-        let mut best_word = state.candidates[0].clone();
-        let mut best_h = -1.0f64;
-        let mut best_is_cand = true;
-        let mut best_exp = f64::INFINITY;
-        let mut best_pwin = -1.0f64;
-
-        for g in &state.candidates {
-            let (h, exp, pwin) = score_guess(g, &state.candidates);
-            if better_guess(
-                h,
-                true,
-                exp,
-                pwin,
-                best_h,
-                best_is_cand,
-                best_exp,
-                best_pwin,
-            ) {
-                best_h = h;
-                best_is_cand = true;
-                best_exp = exp;
-                best_pwin = pwin;
-                best_word = g.clone();
-            }
-        }
-
-        // --- Pass 2: sample extra non-candidates from the full guess list
-        // Use a stride so we cover the list evenly without scanning it all.
-        if non_cand_sample_budget > 0 && !all_words.is_empty() {
-            // How many to *try* sampling (cap by how many non-cands exist).
-            let non_cand_total = all_words.len().saturating_sub(state.candidates.len());
-            let target = non_cand_sample_budget.min(non_cand_total).max(0);
-            if target > 0 {
-                // Ceil division for stride
-                let step = ((all_words.len() + target - 1) / target).max(1);
-                let mut examined = 0usize;
-
-                for i in (0..all_words.len()).step_by(step) {
-                    let g = &all_words[i];
-                    if cand_set.contains(g.as_str()) {
-                        continue; // already evaluated in pass 1
-                    }
-                    let (h, exp, pwin) = score_guess(g, &state.candidates);
-                    if better_guess(
-                        h,
-                        false,
-                        exp,
-                        pwin,
-                        best_h,
-                        best_is_cand,
-                        best_exp,
-                        best_pwin,
-                    ) {
-                        best_h = h;
-                        best_is_cand = false;
-                        best_exp = exp;
-                        best_pwin = pwin;
-                        best_word = g.clone();
-                    }
-                    examined += 1;
-                    if examined >= target {
-                        break;
-                    }
+        let total_weight: f64 = cand_ids.iter().map(|&s| dict.prior().weight(s)).sum();
+        let all_green = Pattern::all_green(dict.word_len()).id();
+        let mut ranked: Vec<(usize, f64)> = Vec::with_capacity(pool.len());
+        for (i, &(g, _)) in pool.iter().enumerate() {
+            if root && self.progress {
+                eprint!("\rLooking ahead... {}/{}", i + 1, pool.len());
+            }
+            let mut cost = 1.0;
+            for (&pid, mask) in dict.masks(g) {
+                if pid == all_green {
+                    continue;
                 }
+                let mut bucket = mask.clone();
+                bucket.intersect_with(candidates);
+                if bucket.is_empty() {
+                    continue;
+                }
+                let weight: f64 = bucket.iter().map(|s| dict.prior().weight(s)).sum();
+                let rest = if depth > 1 {
+                    self.ranked_guesses(&bucket, depth - 1, state, dict, false)
+                        .first()
+                        .map_or(0.0, |&(_, c)| c)
+                } else {
+                    leaf_estimate(bucket.len())
+                };
+                cost += weight / total_weight * rest;
             }
+            ranked.push((g, cost));
+        }
+        if root && self.progress {
+            eprint!("\r{:30}\r", "");
         }
+        // Equal costs keep the entropy order.
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        ranked
+    }
+}
 
-        best_word
+impl Default for LookaheadSolver {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TOP_K, Self::DEFAULT_DEPTH)
     }
 }
 
-impl SolverStrategy for FrequencyAnalyzer {
-    fn suggest_guess(&self, state: &GameState, all_words: &[String]) -> String {
-        // TODO: Implement frequency-based suggestion
-        SimpleSolver.suggest_guess(state, all_words)
+impl SolverStrategy for LookaheadSolver {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            // Looking ahead from the full list is slow; open like EntropyMaximizer.
+            if state.attempt_count == 0 {
+                return EntropyMaximizer::new(self.probes).suggest(state, dict, limit);
+            }
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            self.ranked_guesses(&state.candidates, self.depth, state, dict, true)
+                .into_iter()
+                .take(limit)
+                .map(|(g, _)| Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g)))
+                .collect()
+        })
     }
 }
 
-pub fn create_solver(strategy: &str) -> Box<dyn SolverStrategy> {
-    match strategy {
-        "simple" => Box::new(SimpleSolver),
-        "entropy" => Box::new(EntropyMaximizer),
-        "frequency" => Box::new(FrequencyAnalyzer),
-        _ => Box::new(SimpleSolver),
+impl SurvivalSolver {
+    pub const DEFAULT_TURNS: usize = 6;
+    pub const DEFAULT_TOP_K: usize = 8;
+
+    pub fn new(turns: usize) -> Self {
+        SurvivalSolver {
+            turns: turns.max(1),
+            top_k: Self::DEFAULT_TOP_K,
+            probes: ProbePolicy::default(),
+        }
+    }
+
+    pub fn with_probes(mut self, probes: ProbePolicy) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Guesses left in the game, counting the next one; at least one.
+    pub fn turns_left(&self, state: &GameState) -> usize {
+        self.turns.saturating_sub(state.attempt_count).max(1)
     }
 }
 
-// ======== Scoring helpers ========
+impl Default for SurvivalSolver {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TURNS)
+    }
+}
 
-const WORD_LEN: usize = 5;
-const NUM_PATTERNS: usize = 243; // 3^5
-const PID_ALL_GREEN: usize = 242; // [2,2,2,2,2] in base-3
+impl SolverStrategy for SurvivalSolver {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            let mut ranked = SurvivalSearch::new(self, state, dict).options(
+                &cand_ids,
+                self.turns_left(state),
+                Some(state),
+                false,
+            );
+            // Equal chances keep the shortlist's order.
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+            ranked
+                .into_iter()
+                .take(limit)
+                .map(|(g, _)| Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g)))
+                .collect()
+        })
+    }
 
-/// Return Wordle feedback encoded in base-3 (trits):
-/// 0 = gray, 1 = yellow, 2 = green  => integer in 0..=242.
-/// Two-pass algorithm: mark greens, count remaining letters, then mark yellows.
-#[inline]
-fn feedback_id(guess: &str, solution: &str) -> usize {
-    let g = guess.as_bytes();
-    let s = solution.as_bytes();
-    debug_assert_eq!(g.len(), WORD_LEN);
-    debug_assert_eq!(s.len(), WORD_LEN);
-    if g.len() != WORD_LEN || s.len() != WORD_LEN {
-        return 0; // defensive; your wordlists should always be length 5
-    }
-
-    let mut trits = [0u8; WORD_LEN]; // 0=B, 1=Y, 2=G
-    let mut counts = [0i8; 26]; // remaining unmatched letters in solution
-
-    // Pass 1: greens + count non-green solution letters
-    for i in 0..WORD_LEN {
-        if g[i] == s[i] {
-            trits[i] = 2; // green
-        } else {
-            let idx = (s[i].to_ascii_uppercase() - b'A') as usize;
-            counts[idx] += 1;
+    fn solve_probability(&self, state: &GameState, dict: &Dictionary, guess: &str) -> Option<f64> {
+        let g = dict.index_of(guess)?;
+        let cand_ids: Vec<usize> = state.candidates.iter().collect();
+        let probs = dict.prior().probabilities(&cand_ids);
+        let turns = self.turns_left(state);
+        Some(SurvivalSearch::new(self, state, dict).guess_probability(g, &cand_ids, &probs, turns))
+    }
+}
+
+/// One `SurvivalSolver` suggestion's search. Chances are memoized on the
+/// candidate list and turns left.
+struct SurvivalSearch<'a> {
+    top_k: usize,
+    probes: ProbePolicy,
+    dict: &'a Dictionary,
+    hard_mode: bool,
+    memo: HashMap<(Vec<usize>, usize), f64>,
+}
+
+impl<'a> SurvivalSearch<'a> {
+    fn new(solver: &SurvivalSolver, state: &GameState, dict: &'a Dictionary) -> Self {
+        SurvivalSearch {
+            top_k: solver.top_k,
+            probes: solver.probes,
+            dict,
+            hard_mode: state.hard_mode,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Best chance of finding one of `cands` within `turns` guesses.
+    fn win_probability(&mut self, cands: &[usize], turns: usize) -> f64 {
+        let n = cands.len();
+        if n == 0 || turns == 0 {
+            return 0.0;
+        }
+        // Guessing the candidates one by one finds the answer in `n` turns.
+        if n <= turns {
+            return 1.0;
+        }
+        let key = (cands.to_vec(), turns);
+        if let Some(&p) = self.memo.get(&key) {
+            return p;
         }
+        let best = self
+            .options(cands, turns, None, true)
+            .iter()
+            .map(|&(_, p)| p)
+            .fold(0.0, f64::max);
+        self.memo.insert(key, best);
+        best
     }
-    // Pass 2: yellows where counts remain
-    for i in 0..WORD_LEN {
-        if trits[i] == 0 {
-            let idx = (g[i].to_ascii_uppercase() - b'A') as usize;
-            if counts[idx] > 0 {
-                trits[i] = 1; // yellow
-                counts[idx] -= 1;
+
+    /// Guesses worth trying for `cands` and their chance of finding the answer
+    /// within `turns`. At the root, `root` holds the game, whose hints limit
+    /// the guesses in hard mode; deeper plies then stick to candidates. With
+    /// `first_sure`, stop at the first guess that is certain to win in time.
+    fn options(
+        &mut self,
+        cands: &[usize],
+        turns: usize,
+        root: Option<&GameState>,
+        first_sure: bool,
+    ) -> Vec<(usize, f64)> {
+        let dict = self.dict;
+        let probs = dict.prior().probabilities(cands);
+        if turns == 1 || cands.len() <= 2 {
+            // Only a candidate can win now; the likeliest first.
+            let sure = cands.len() <= turns;
+            let mut ranked: Vec<(usize, f64)> = cands
+                .iter()
+                .zip(&probs)
+                .map(|(&s, &p)| (s, if sure { 1.0 } else { p }))
+                .collect();
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+            return ranked;
+        }
+
+        let hard_mode = self.hard_mode;
+        let allowed = |g: usize| match root {
+            Some(state) => state.allows_guess(dict.word(g)),
+            None => !hard_mode || cands.binary_search(&g).is_ok(),
+        };
+        if turns == 2 {
+            return last_split(cands, &probs, dict, allowed);
+        }
+
+        let mut options = Vec::new();
+        for g in self.shortlist(cands, root.is_none(), allowed) {
+            let p = self.guess_probability(g, cands, &probs, turns);
+            options.push((g, p));
+            if first_sure && p >= 1.0 - 1e-12 {
+                break;
             }
         }
+        options
     }
 
-    // Encode five trits in base-3
-    let mut id = 0usize;
-    for &t in &trits {
-        id = id * 3 + t as usize;
+    /// Chance of finding one of `cands` (with probabilities `probs`) within
+    /// `turns` guesses by playing `g` first, then the best guess after each
+    /// feedback.
+    fn guess_probability(&mut self, g: usize, cands: &[usize], probs: &[f64], turns: usize) -> f64 {
+        let mut p = 0.0;
+        let mut buckets: HashMap<usize, (Vec<usize>, f64)> = HashMap::new();
+        for (&s, &ps) in cands.iter().zip(probs) {
+            if s == g {
+                p += ps;
+            } else {
+                let bucket = buckets.entry(self.dict.table().get(g, s)).or_default();
+                bucket.0.push(s);
+                bucket.1 += ps;
+            }
+        }
+        for (bucket, mass) in buckets.into_values() {
+            p += mass * self.win_probability(&bucket, turns - 1);
+        }
+        p
+    }
+
+    /// The `top_k` allowed guesses with the most information and the `top_k`
+    /// with the smallest worst case, in that order, without repeats.
+    fn shortlist(
+        &self,
+        cands: &[usize],
+        nested: bool,
+        allowed: impl Fn(usize) -> bool,
+    ) -> Vec<usize> {
+        let dict = self.dict;
+        let candidates = CandidateSet::from_indices(dict.num_answers(), cands.iter().copied());
+        let probes = match (nested, self.hard_mode) {
+            (false, _) => self.probes,
+            (true, false) => self.probes.with_budget(NESTED_PROBE_BUDGET),
+            (true, true) => self.probes.with_budget(0),
+        };
+        let mut pool = scored_pool(&candidates, cands, dict, &probes, allowed);
+        rank_pool(&mut pool);
+
+        let mut shortlist: Vec<usize> = pool.iter().take(self.top_k).map(|&(g, _)| g).collect();
+        let table = dict.table();
+        let mut by_worst: Vec<(usize, usize)> = pool
+            .iter()
+            .map(|&(g, _)| {
+                let counts = bucket_counts(cands.iter().map(|&s| table.get(g, s)), dict.word_len());
+                (counts.iter().map(|&(_, c)| c).max().unwrap_or(0), g)
+            })
+            .collect();
+        by_worst.sort_by_key(|&(worst, _)| worst);
+        for (_, g) in by_worst.into_iter().take(self.top_k) {
+            if !shortlist.contains(&g) {
+                shortlist.push(g);
+            }
+        }
+        shortlist
     }
-    id
 }
 
-/// Score a guess against the current candidate set.
-/// Returns (entropy in bits, expected remaining |C'|, win probability).
+/// With two turns left: every allowed guess by its chance of winning in time,
+/// which is the guess's own chance plus, for each feedback, that of the
+/// likeliest answer left.
+fn last_split(
+    cands: &[usize],
+    probs: &[f64],
+    dict: &Dictionary,
+    allowed: impl Fn(usize) -> bool,
+) -> Vec<(usize, f64)> {
+    let table = dict.table();
+    let mut top = vec![0.0f64; num_patterns(dict.word_len())];
+    let mut options = Vec::new();
+    for g in (0..dict.num_guesses()).filter(|&g| allowed(g)) {
+        for (&s, &p) in cands.iter().zip(probs) {
+            let t = &mut top[table.get(g, s)];
+            *t = t.max(p);
+        }
+        let mut p = 0.0;
+        for &s in cands {
+            p += std::mem::take(&mut top[table.get(g, s)]);
+        }
+        options.push((g, p));
+    }
+    options
+}
+
+/// Rough guesses to finish for `n` candidates past the lookahead horizon: one
+/// guess per `LEAF_BITS_PER_GUESS` bits, but never better than guessing
+/// candidates that split the rest perfectly.
+fn leaf_estimate(n: usize) -> f64 {
+    match n {
+        0 => 0.0,
+        1 => 1.0,
+        n => {
+            let n = n as f64;
+            ((2.0 * n - 1.0) / n).max(1.0 + n.log2() / LEAF_BITS_PER_GUESS)
+        }
+    }
+}
+
+/// Rollouts that run this long count as lost at this many guesses.
+const ROLLOUT_MAX_TURNS: usize = 12;
+
+impl MctsSolver {
+    pub const DEFAULT_ITERATIONS: usize = 2000;
+    pub const DEFAULT_ACTIONS: usize = 32;
+    pub const DEFAULT_SEED: u64 = 0;
+
+    pub fn new(iterations: usize) -> Self {
+        MctsSolver {
+            iterations: iterations.max(1),
+            time_limit: None,
+            actions: Self::DEFAULT_ACTIONS,
+            exploration: 1.0,
+            seed: Self::DEFAULT_SEED,
+            rollout: Box::new(FrequencyAnalyzer::new()),
+        }
+    }
+
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_rollout(mut self, rollout: Box<dyn SolverStrategy>) -> Self {
+        self.rollout = rollout;
+        self
+    }
+
+    /// Guesses to finish once `guess` is played against `answer`, counting it.
+    fn playout(&self, state: &GameState, guess: usize, answer: usize, dict: &Dictionary) -> usize {
+        let target = dict.word(answer);
+        let mut state = state.clone();
+        let mut word = dict.word(guess).to_string();
+        for turn in 1..=ROLLOUT_MAX_TURNS {
+            if word == target {
+                return turn;
+            }
+            let guess = Guess::new(word.clone(), calculate_feedback(&word, target));
+            dict.filter(&mut state.candidates, &guess);
+            state.add_attempt(guess);
+            word = self.rollout.suggest_guess(&state, dict);
+            if word.is_empty() {
+                break;
+            }
+        }
+        ROLLOUT_MAX_TURNS
+    }
+}
+
+impl Default for MctsSolver {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ITERATIONS)
+    }
+}
+
+impl SolverStrategy for MctsSolver {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || self.search(state, dict, limit))
+    }
+}
+
+impl MctsSolver {
+    /// Shortlisted guesses by average guesses over their rollouts, fewest first.
+    fn search(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        let cand_ids: Vec<usize> = state.candidates.iter().collect();
+        let probs = dict.prior().probabilities(&cand_ids);
+        if cand_ids.len() <= 2 {
+            // Likelier first.
+            let mut order: Vec<usize> = (0..cand_ids.len()).collect();
+            order.sort_by(|&a, &b| probs[b].total_cmp(&probs[a]));
+            return order
+                .into_iter()
+                .take(limit)
+                .map(|i| Suggestion::new(cand_ids[i], &cand_ids, dict, true))
+                .collect();
+        }
+
+        let mut actions = FrequencyAnalyzer::with_probes().scores(state, dict);
+        actions.sort_by(|a, b| b.1.total_cmp(&a.1));
+        actions.truncate(self.actions.max(1));
+
+        // Answers are sampled by the prior through its cumulative distribution.
+        let cumulative: Vec<f64> = probs
+            .iter()
+            .scan(0.0, |acc, p| {
+                *acc += p;
+                Some(*acc)
+            })
+            .collect();
+        let mut rng = Rng::new(self.seed);
+        let start = Instant::now();
+        let mut visits = vec![0usize; actions.len()];
+        let mut totals = vec![0usize; actions.len()];
+        for iteration in 0..self.iterations {
+            if self
+                .time_limit
+                .is_some_and(|limit| start.elapsed() >= limit)
+            {
+                break;
+            }
+            // Every guess is tried once, then UCB1 on the fewest guesses.
+            let a = if iteration < actions.len() {
+                iteration
+            } else {
+                let log_n = (iteration as f64).ln();
+                (0..actions.len())
+                    .min_by(|&x, &y| {
+                        let ucb = |i: usize| {
+                            totals[i] as f64 / visits[i] as f64
+                                - self.exploration * (log_n / visits[i] as f64).sqrt()
+                        };
+                        ucb(x).total_cmp(&ucb(y))
+                    })
+                    .unwrap()
+            };
+            let r = rng.next_f64() * cumulative[cumulative.len() - 1];
+            let answer = cand_ids[cumulative
+                .partition_point(|&c| c <= r)
+                .min(cand_ids.len() - 1)];
+            totals[a] += self.playout(state, actions[a].0, answer, dict);
+            visits[a] += 1;
+        }
+
+        // Fewest guesses on average, among guesses sampled often enough for the
+        // average to mean something; the rarely sampled ones follow.
+        let most = visits.iter().copied().max().unwrap_or(0);
+        let trusted = |i: usize| visits[i] > 0 && visits[i] * 4 >= most;
+        let mean = |i: usize| match visits[i] {
+            0 => f64::INFINITY,
+            n => totals[i] as f64 / n as f64,
+        };
+        let mut order: Vec<usize> = (0..actions.len()).collect();
+        order.sort_by(|&x, &y| {
+            trusted(y)
+                .cmp(&trusted(x))
+                .then(mean(x).total_cmp(&mean(y)))
+                .then(visits[y].cmp(&visits[x]))
+        });
+        order
+            .into_iter()
+            .take(limit)
+            .map(|i| {
+                let g = actions[i].0;
+                Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g))
+            })
+            .collect()
+    }
+}
+
+impl SolverStrategy for TreeSolver {
+    /// The tree has one move per position, so it is the only suggestion.
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        match self.tree.next_guess(&state.attempts) {
+            Some(guess) => vec![Suggestion::score(guess, state, dict)]
+                .into_iter()
+                .take(limit)
+                .collect(),
+            None => EntropyMaximizer::new(self.probes).suggest(state, dict, limit),
+        }
+    }
+}
+
+impl SolverStrategy for MinimaxSolver {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        book_first(state, dict, limit, || {
+            let cand_ids: Vec<usize> = state.candidates.iter().collect();
+            if cand_ids.len() <= 2 {
+                return candidates_in_order(&cand_ids, dict, limit);
+            }
+
+            // Every allowed guess: a worst-case bound is only as good as the pool.
+            let mut scored: Vec<(usize, MinimaxScore)> = (0..dict.num_guesses())
+                .filter_map(|g| {
+                    let is_candidate = state.candidates.contains(g);
+                    if !is_candidate && !state.allows_guess(dict.word(g)) {
+                        return None;
+                    }
+                    Some((g, MinimaxScore::new(g, &cand_ids, dict, is_candidate)))
+                })
+                .collect();
+            scored.sort_by_key(|(_, score)| std::cmp::Reverse(score.key()));
+            scored
+                .iter()
+                .take(limit)
+                .map(|(g, score)| Suggestion::new(*g, &cand_ids, dict, score.is_candidate))
+                .collect()
+        })
+    }
+}
+
+impl SolverStrategy for AbsurdleSolver {
+    fn suggest(&self, state: &GameState, dict: &Dictionary, limit: usize) -> Vec<Suggestion> {
+        let cand_ids: Vec<usize> = state.candidates.iter().collect();
+        let (_, best) =
+            AbsurdleSearch::new(dict, state.hard_mode).best_guesses(&state.candidates, limit);
+        best.into_iter()
+            .map(|g| Suggestion::new(g, &cand_ids, dict, state.candidates.contains(g)))
+            .collect()
+    }
+}
+
+pub fn create_solver(strategy: &str) -> Box<dyn SolverStrategy> {
+    match strategy {
+        "simple" => Box::new(SimpleSolver),
+        "entropy" => Box::new(EntropyMaximizer::default()),
+        "minimax" => Box::new(MinimaxSolver),
+        "mcts" => Box::new(MctsSolver::default()),
+        "lookahead" => Box::new(LookaheadSolver::default().with_progress(true)),
+        "survival" => Box::new(SurvivalSolver::default()),
+        "absurdle" => Box::new(AbsurdleSolver),
+        "frequency" => Box::new(FrequencyAnalyzer::new()),
+        "frequency-probes" => Box::new(FrequencyAnalyzer::with_probes()),
+        _ => Box::new(SimpleSolver),
+    }
+}
+
+// ======== Scoring helpers ========
+
+/// Score a guess (guess index) against the current candidate set (answer indices).
+/// Returns (entropy in bits, expected remaining |C'|, win probability), weighted by
+/// the dictionary's prior.
 #[inline]
-fn score_guess(guess: &str, candidates: &[String]) -> (f64, f64, f64) {
-    let mut hist = [0usize; NUM_PATTERNS];
+pub fn score_guess(guess: usize, candidates: &[usize], dict: &Dictionary) -> (f64, f64, f64) {
+    let table = dict.table();
+    score_against(candidates.iter().map(|&s| (s, table.get(guess, s))), dict)
+}
+
+/// Score `(answer index, pattern id)` pairs for one guess, counting every answer
+/// equally under a uniform prior and weighting by the prior otherwise.
+pub fn score_against<I: Iterator<Item = (usize, usize)>>(
+    pairs: I,
+    dict: &Dictionary,
+) -> (f64, f64, f64) {
+    let prior = dict.prior();
+    if prior.is_uniform() {
+        score_patterns(pairs.map(|(_, pid)| pid), dict.word_len())
+    } else {
+        score_weighted(
+            pairs.map(|(s, pid)| (pid, prior.weight(s))),
+            dict.word_len(),
+        )
+    }
+}
 
-    for s in candidates {
-        let pid = feedback_id(guess, s);
-        // SAFETY: pid is 0..=242
-        hist[pid] += 1;
+/// Score the feedback ids a guess produces, one per candidate solution.
+/// Returns (entropy in bits, expected remaining |C'|, win probability).
+pub fn score_patterns<I: Iterator<Item = usize>>(pids: I, word_len: usize) -> (f64, f64, f64) {
+    let buckets = bucket_counts(pids, word_len);
+    let total: usize = buckets.iter().map(|&(_, c)| c).sum();
+    if total == 0 {
+        return (0.0, 0.0, 0.0);
     }
 
-    let n = candidates.len() as f64;
+    let n = total as f64;
     let mut h = 0.0;
     let mut sum_sq: usize = 0;
+    let mut wins = 0usize;
+    let all_green = Pattern::all_green(word_len).id();
 
-    for &c in &hist {
-        if c > 0 {
-            let p = c as f64 / n;
-            h -= p * p.log2(); // Shannon entropy of feedback
-            sum_sq += c * c;
+    for &(pid, c) in &buckets {
+        let p = c as f64 / n;
+        h -= p * p.log2(); // Shannon entropy of feedback
+        sum_sq += c * c;
+        if pid == all_green {
+            wins = c;
         }
     }
     let expect_remaining = sum_sq as f64 / n;
-    let p_win = hist[PID_ALL_GREEN] as f64 / n;
+    let p_win = wins as f64 / n;
     (h, expect_remaining, p_win)
 }
 
-/// Tie-break order:
-/// 1) Higher entropy (more information expected)
-/// 2) If equal entropy: prefer guesses that are in the candidate set
-/// 3) If still equal: smaller expected remaining candidates
-/// 4) If still equal: higher probability to win immediately
-#[inline]
-fn better_guess(
-    h: f64,
-    is_cand: bool,
-    exp: f64,
-    pwin: f64,
-    best_h: f64,
-    best_is_cand: bool,
-    best_exp: f64,
-    best_pwin: f64,
-) -> bool {
-    h > best_h
-        || (h == best_h && is_cand && !best_is_cand)
-        || (h == best_h && is_cand == best_is_cand && exp < best_exp)
-        || (h == best_h && is_cand == best_is_cand && exp == best_exp && pwin > best_pwin)
+/// Like `score_patterns` with a weight per candidate: p_r is the bucket's share
+/// of the total weight, expected remaining is Σ p_r·|C_r| and P(win) is the
+/// all-green bucket's share.
+pub fn score_weighted<I: Iterator<Item = (usize, f64)>>(
+    items: I,
+    word_len: usize,
+) -> (f64, f64, f64) {
+    let mut items: Vec<(usize, f64)> = items.collect();
+    items.sort_unstable_by_key(|&(pid, _)| pid);
+    // (pid, weight, count) per bucket
+    let mut buckets: Vec<(usize, f64, usize)> = Vec::new();
+    for (pid, w) in items {
+        match buckets.last_mut() {
+            Some((last, weight, count)) if *last == pid => {
+                *weight += w;
+                *count += 1;
+            }
+            _ => buckets.push((pid, w, 1)),
+        }
+    }
+    let total: f64 = buckets.iter().map(|&(_, w, _)| w).sum();
+    if total <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let all_green = Pattern::all_green(word_len).id();
+    let mut h = 0.0;
+    let mut expect_remaining = 0.0;
+    let mut p_win = 0.0;
+    for &(pid, w, c) in &buckets {
+        let p = w / total;
+        if p > 0.0 {
+            h -= p * p.log2();
+        }
+        expect_remaining += p * c as f64;
+        if pid == all_green {
+            p_win = p;
+        }
+    }
+    (h, expect_remaining, p_win)
+}
+
+/// Largest word length whose 3^L histogram is kept as a flat array.
+const DENSE_HISTOGRAM_MAX_LEN: usize = 8;
+
+/// Non-empty feedback buckets as (pattern id, size), in pattern id order.
+/// Short words use a flat 3^L histogram; long words sort the ids instead of
+/// allocating hundreds of thousands of mostly-empty bins.
+pub fn bucket_counts<I: Iterator<Item = usize>>(pids: I, word_len: usize) -> Vec<(usize, usize)> {
+    if word_len <= DENSE_HISTOGRAM_MAX_LEN {
+        let mut hist = vec![0usize; num_patterns(word_len)];
+        for pid in pids {
+            hist[pid] += 1;
+        }
+        return hist
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, c)| c > 0)
+            .collect();
+    }
+
+    let mut ids: Vec<usize> = pids.collect();
+    ids.sort_unstable();
+    let mut buckets: Vec<(usize, usize)> = Vec::new();
+    for pid in ids {
+        match buckets.last_mut() {
+            Some((last, c)) if *last == pid => *c += 1,
+            _ => buckets.push((pid, 1)),
+        }
+    }
+    buckets
+}
+
+/// Metrics of one scored guess, compared with `better_than`.
+#[derive(Debug, Clone, Copy)]
+struct GuessScore {
+    entropy: f64,
+    is_candidate: bool,
+    expected_remaining: f64,
+    p_win: f64,
+}
+
+impl GuessScore {
+    fn new(guess: usize, candidates: &[usize], dict: &Dictionary, is_candidate: bool) -> Self {
+        let (entropy, expected_remaining, p_win) = score_guess(guess, candidates, dict);
+        GuessScore {
+            entropy,
+            is_candidate,
+            expected_remaining,
+            p_win,
+        }
+    }
+
+    /// Tie-break order:
+    /// 1) Higher entropy (more information expected)
+    /// 2) If equal entropy: prefer guesses that are in the candidate set
+    /// 3) If still equal: smaller expected remaining candidates
+    /// 4) If still equal: higher probability to win immediately
+    #[inline]
+    fn better_than(&self, best: &GuessScore) -> bool {
+        let (h, best_h) = (self.entropy, best.entropy);
+        let (is_cand, best_is_cand) = (self.is_candidate, best.is_candidate);
+        let (exp, best_exp) = (self.expected_remaining, best.expected_remaining);
+        h > best_h
+            || (h == best_h && is_cand && !best_is_cand)
+            || (h == best_h && is_cand == best_is_cand && exp < best_exp)
+            || (h == best_h
+                && is_cand == best_is_cand
+                && exp == best_exp
+                && self.p_win > best.p_win)
+    }
+}
+
+/// Worst-case metrics of one guess, compared by `key`.
+#[derive(Debug, Clone, Copy)]
+struct MinimaxScore {
+    largest_bucket: usize,
+    is_candidate: bool,
+    num_buckets: usize,
+    /// Σ|C_r|² (proportional to the expected remaining size).
+    sum_sq: usize,
+}
+
+impl MinimaxScore {
+    fn new(guess: usize, candidates: &[usize], dict: &Dictionary, is_candidate: bool) -> Self {
+        let table = dict.table();
+        let buckets = bucket_counts(
+            candidates.iter().map(|&s| table.get(guess, s)),
+            dict.word_len(),
+        );
+        MinimaxScore {
+            largest_bucket: buckets.iter().map(|&(_, c)| c).max().unwrap_or(0),
+            is_candidate,
+            num_buckets: buckets.len(),
+            sum_sq: buckets.iter().map(|&(_, c)| c * c).sum(),
+        }
+    }
+
+    /// Ordering key, greater is better:
+    /// 1) Smaller largest bucket (fewer candidates left in the worst case)
+    /// 2) If equal: prefer guesses that are in the candidate set
+    /// 3) If still equal: more distinct feedback patterns
+    /// 4) If still equal: smaller expected remaining candidates
+    #[inline]
+    fn key(&self) -> impl Ord {
+        (
+            std::cmp::Reverse(self.largest_bucket),
+            self.is_candidate,
+            self.num_buckets,
+            std::cmp::Reverse(self.sum_sq),
+        )
+    }
 }
@@ -0,0 +1,256 @@
+use crate::wordle::alphabet::{word_length, Alphabet};
+use crate::wordle::core::feedback_letters;
+use crate::wordle::pattern::num_patterns;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// File header; bump the version whenever the pattern encoding changes.
+const MAGIC: &[u8; 4] = b"WPT2";
+
+/// Pattern ids stored with the narrowest integer that holds 3^L values:
+/// one byte per pair up to 5 letters, two up to 10, four beyond.
+enum Cells {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Cells {
+    fn with_capacity(word_len: usize, n: usize) -> Self {
+        match num_patterns(word_len) {
+            p if p <= 1 << 8 => Cells::U8(Vec::with_capacity(n)),
+            p if p <= 1 << 16 => Cells::U16(Vec::with_capacity(n)),
+            _ => Cells::U32(Vec::with_capacity(n)),
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Cells::U8(_) => 1,
+            Cells::U16(_) => 2,
+            Cells::U32(_) => 4,
+        }
+    }
+
+    fn push(&mut self, pid: usize) {
+        match self {
+            Cells::U8(v) => v.push(pid as u8),
+            Cells::U16(v) => v.push(pid as u16),
+            Cells::U32(v) => v.push(pid as u32),
+        }
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> usize {
+        match self {
+            Cells::U8(v) => v[i] as usize,
+            Cells::U16(v) => v[i] as usize,
+            Cells::U32(v) => v[i] as usize,
+        }
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        match self {
+            Cells::U8(v) => v.clone(),
+            Cells::U16(v) => v.iter().flat_map(|x| x.to_le_bytes()).collect(),
+            Cells::U32(v) => v.iter().flat_map(|x| x.to_le_bytes()).collect(),
+        }
+    }
+
+    fn from_le_bytes(width: usize, bytes: Vec<u8>) -> Option<Self> {
+        match width {
+            1 => Some(Cells::U8(bytes)),
+            2 => Some(Cells::U16(
+                bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect(),
+            )),
+            4 => Some(Cells::U32(
+                bytes
+                    .chunks_exact(4)
+                    .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Precomputed feedback id for every (guess, solution) pair.
+/// Rows are guesses, columns are solutions, both indexed by dictionary position.
+pub struct PatternTable {
+    guesses: usize,
+    solutions: usize,
+    word_len: usize,
+    cells: Cells,
+}
+
+impl PatternTable {
+    /// Compute every pair from scratch.
+    pub fn build(guesses: &[String], solutions: &[String]) -> Self {
+        let word_len = guesses
+            .first()
+            .or(solutions.first())
+            .map_or(0, |w| word_length(w));
+        // Words as dense letter indices, so every pair compares integers.
+        let alphabet = Alphabet::new(guesses.iter().chain(solutions).flat_map(|w| w.chars()));
+        let letters = |words: &[String]| -> Vec<Vec<usize>> {
+            words
+                .iter()
+                .map(|w| w.chars().filter_map(|c| alphabet.index_of(c)).collect())
+                .collect()
+        };
+        let (guess_letters, solution_letters) = (letters(guesses), letters(solutions));
+
+        let mut cells = Cells::with_capacity(word_len, guesses.len() * solutions.len());
+        for g in &guess_letters {
+            for s in &solution_letters {
+                cells.push(feedback_letters(g, s));
+            }
+        }
+        PatternTable {
+            guesses: guesses.len(),
+            solutions: solutions.len(),
+            word_len,
+            cells,
+        }
+    }
+
+    /// Read the table from the cache directory, or build it and write it there.
+    /// A cache file is only used if it was built from the same word lists.
+    pub fn load_or_build(guesses: &[String], solutions: &[String]) -> Self {
+        let hash = dictionary_hash(guesses, solutions);
+        let path = cache_dir().join(format!("patterns-{:016x}.bin", hash));
+
+        if let Ok(table) = Self::load(&path, hash) {
+            if table.guesses == guesses.len() && table.solutions == solutions.len() {
+                return table;
+            }
+        }
+
+        let table = Self::build(guesses, solutions);
+        if let Err(e) = table.save(&path, hash) {
+            eprintln!(
+                "⚠️  Could not write pattern cache {}: {}",
+                path.display(),
+                e
+            );
+        }
+        table
+    }
+
+    pub fn load(path: &Path, hash: u64) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = [0u8; 22];
+        reader.read_exact(&mut header)?;
+
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        if &header[0..4] != MAGIC {
+            return Err(invalid("not a pattern table"));
+        }
+        if u64::from_le_bytes(header[4..12].try_into().unwrap()) != hash {
+            return Err(invalid("pattern table was built for another dictionary"));
+        }
+        let guesses = u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize;
+        let solutions = u32::from_le_bytes(header[16..20].try_into().unwrap()) as usize;
+        let word_len = header[20] as usize;
+        let width = header[21] as usize;
+
+        let mut bytes = Vec::with_capacity(guesses * solutions * width);
+        reader.read_to_end(&mut bytes)?;
+        if bytes.len() != guesses * solutions * width {
+            return Err(invalid("truncated pattern table"));
+        }
+        let cells =
+            Cells::from_le_bytes(width, bytes).ok_or_else(|| invalid("unknown cell width"))?;
+
+        Ok(PatternTable {
+            guesses,
+            solutions,
+            word_len,
+            cells,
+        })
+    }
+
+    pub fn save(&self, path: &Path, hash: u64) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first so a crash never leaves a torn cache.
+        let tmp = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            writer.write_all(MAGIC)?;
+            writer.write_all(&hash.to_le_bytes())?;
+            writer.write_all(&(self.guesses as u32).to_le_bytes())?;
+            writer.write_all(&(self.solutions as u32).to_le_bytes())?;
+            writer.write_all(&[self.word_len as u8, self.cells.width() as u8])?;
+            writer.write_all(&self.cells.to_le_bytes())?;
+            writer.flush()?;
+        }
+        fs::rename(tmp, path)
+    }
+
+    /// Pattern id for guess row `guess` against solution column `solution`.
+    #[inline]
+    pub fn get(&self, guess: usize, solution: usize) -> usize {
+        self.cells.get(guess * self.solutions + solution)
+    }
+
+    /// All pattern ids for one guess, in solution order.
+    pub fn row(&self, guess: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = guess * self.solutions;
+        (start..start + self.solutions).map(move |i| self.cells.get(i))
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    pub fn num_guesses(&self) -> usize {
+        self.guesses
+    }
+
+    pub fn num_solutions(&self) -> usize {
+        self.solutions
+    }
+}
+
+/// FNV-1a hash of both word lists (order matters, since the table is positional).
+pub fn dictionary_hash(guesses: &[String], solutions: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    feed(MAGIC);
+    for list in [guesses, solutions] {
+        for w in list {
+            feed(w.as_bytes());
+            feed(b"\n");
+        }
+        feed(&[0xff]);
+    }
+    hash
+}
+
+/// `$WORDLE_CACHE_DIR`, else `$XDG_CACHE_HOME/wordle`, else `~/.cache/wordle`,
+/// else the system temp directory.
+pub fn cache_dir() -> PathBuf {
+    use std::env;
+
+    if let Some(dir) = env::var_os("WORDLE_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        return PathBuf::from(dir).join("wordle");
+    }
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home).join(".cache").join("wordle");
+    }
+    env::temp_dir().join("wordle")
+}
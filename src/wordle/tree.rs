@@ -0,0 +1,513 @@
+use crate::wordle::alphabet::normalize_word;
+use crate::wordle::dictionary::Dictionary;
+use crate::wordle::pattern::{num_patterns, Pattern};
+use crate::wordle::types::Guess;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Classic Wordle allows six guesses.
+pub const MAX_DEPTH: usize = 6;
+
+/// One guess of a strategy tree and the subtree for each feedback it can get.
+/// The all-green feedback ends the game and has no branch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    pub guess: String,
+    /// Sorted by pattern id.
+    pub branches: Vec<(Pattern, TreeNode)>,
+}
+
+impl TreeNode {
+    fn branch(&self, feedback: Pattern) -> Option<&TreeNode> {
+        self.branches
+            .iter()
+            .find(|(p, _)| *p == feedback)
+            .map(|(_, node)| node)
+    }
+}
+
+/// A complete strategy: for every answer, the guesses that find it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionTree {
+    pub word_len: usize,
+    /// Guess limit the tree was built under.
+    pub max_depth: usize,
+    /// Answers the tree finds.
+    pub answers: usize,
+    /// Guesses summed over every answer, the winning ones included.
+    pub total_guesses: usize,
+    pub root: TreeNode,
+}
+
+impl DecisionTree {
+    pub fn opener(&self) -> &str {
+        &self.root.guess
+    }
+
+    /// Average guesses per answer.
+    pub fn average(&self) -> f64 {
+        if self.answers == 0 {
+            return 0.0;
+        }
+        self.total_guesses as f64 / self.answers as f64
+    }
+
+    /// The tree's next guess after `attempts`, or None once play has left it.
+    pub fn next_guess(&self, attempts: &[Guess]) -> Option<&str> {
+        let mut node = &self.root;
+        for attempt in attempts {
+            if attempt.word != node.guess {
+                return None;
+            }
+            node = node.branch(attempt.feedback)?;
+        }
+        Some(&node.guess)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{{");
+        let _ = writeln!(out, "  \"word_len\": {},", self.word_len);
+        let _ = writeln!(out, "  \"max_depth\": {},", self.max_depth);
+        let _ = writeln!(out, "  \"answers\": {},", self.answers);
+        let _ = writeln!(out, "  \"total_guesses\": {},", self.total_guesses);
+        out.push_str("  \"tree\": ");
+        write_node(&mut out, &self.root, 1);
+        out.push_str("\n}\n");
+        out
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let mut parser = JsonParser::new(text);
+        let value = parser.value()?;
+        parser.end()?;
+
+        let field = |name: &str| value.get(name).ok_or(format!("Missing \"{}\"", name));
+        let number = |name: &str| -> Result<usize, String> {
+            field(name)?
+                .as_usize()
+                .ok_or(format!("\"{}\" must be a whole number", name))
+        };
+        let word_len = number("word_len")?;
+        let tree = DecisionTree {
+            word_len,
+            max_depth: number("max_depth")?,
+            answers: number("answers")?,
+            total_guesses: number("total_guesses")?,
+            root: read_node(field("tree")?, word_len)?,
+        };
+        Ok(tree)
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+        Self::from_json(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+fn write_node(out: &mut String, node: &TreeNode, indent: usize) {
+    let _ = write!(out, "{{\"guess\": \"{}\"", node.guess);
+    if !node.branches.is_empty() {
+        out.push_str(", \"next\": {");
+        for (i, (pattern, child)) in node.branches.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "\n{}\"{}\": ", "  ".repeat(indent + 1), pattern);
+            write_node(out, child, indent + 1);
+        }
+        let _ = write!(out, "\n{}}}", "  ".repeat(indent));
+    }
+    out.push('}');
+}
+
+fn read_node(value: &Json, word_len: usize) -> Result<TreeNode, String> {
+    let guess = value
+        .get("guess")
+        .and_then(Json::as_str)
+        .ok_or("Every node needs a \"guess\" string")?;
+    let guess = normalize_word(guess);
+    if guess.chars().count() != word_len {
+        return Err(format!("{} is not {} letters", guess, word_len));
+    }
+    let mut branches = Vec::new();
+    if let Some(next) = value.get("next") {
+        let Json::Object(fields) = next else {
+            return Err(format!("\"next\" of {} must be an object", guess));
+        };
+        for (key, child) in fields {
+            let pattern: Pattern = key.parse()?;
+            if pattern.len() != word_len || pattern.is_all_green() {
+                return Err(format!("Invalid feedback {} after {}", key, guess));
+            }
+            branches.push((pattern, read_node(child, word_len)?));
+        }
+    }
+    branches.sort_by_key(|(p, _)| p.id());
+    Ok(TreeNode { guess, branches })
+}
+
+/// Builds the tree with the fewest total guesses for a fixed opener, by
+/// depth-first branch-and-bound. Subproblems are memoized on their candidate
+/// list and remaining depth.
+pub struct TreeSearch<'a> {
+    dict: &'a Dictionary,
+    max_depth: usize,
+    /// Guesses tried per node, best first by sum of squared bucket sizes; None
+    /// tries every allowed guess, which makes the result exact.
+    width: Option<usize>,
+    progress: bool,
+    memo: HashMap<(Vec<usize>, usize), Solved>,
+}
+
+/// What is known about a subproblem.
+#[derive(Debug, Clone, Copy)]
+enum Solved {
+    /// Optimal total guesses and the guess index achieving it.
+    Exact(usize, usize),
+    /// No tree costs less than this.
+    AtLeast(usize),
+}
+
+impl<'a> TreeSearch<'a> {
+    pub fn new(dict: &'a Dictionary, max_depth: usize, width: Option<usize>) -> Self {
+        TreeSearch {
+            dict,
+            max_depth,
+            width,
+            progress: false,
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// The best tree opening with `opener` that finds every answer within
+    /// `max_depth` guesses.
+    pub fn build(&mut self, opener: &str) -> Result<DecisionTree, String> {
+        let opener = normalize_word(opener);
+        let g = self
+            .dict
+            .index_of(&opener)
+            .ok_or_else(|| format!("{} is not an allowed guess", opener))?;
+        let all: Vec<usize> = (0..self.dict.num_answers()).collect();
+        let buckets = self.partition(g, &all);
+
+        let mut total = all.len();
+        for (i, (_, bucket)) in buckets.iter().enumerate() {
+            if self.progress {
+                eprint!("\r  Solving bucket {}/{}...", i + 1, buckets.len());
+            }
+            let (cost, _) = self
+                .solve(bucket, self.max_depth - 1, usize::MAX)
+                .ok_or_else(|| {
+                    format!(
+                        "No tree after {} finds every answer within {} guesses",
+                        opener, self.max_depth
+                    )
+                })?;
+            total += cost;
+        }
+        if self.progress {
+            eprintln!();
+        }
+
+        let root = TreeNode {
+            guess: opener,
+            branches: self.branches(&buckets, self.max_depth - 1),
+        };
+        Ok(DecisionTree {
+            word_len: self.dict.word_len(),
+            max_depth: self.max_depth,
+            answers: all.len(),
+            total_guesses: total,
+            root,
+        })
+    }
+
+    fn branches(
+        &mut self,
+        buckets: &[(usize, Vec<usize>)],
+        depth: usize,
+    ) -> Vec<(Pattern, TreeNode)> {
+        let word_len = self.dict.word_len();
+        buckets
+            .iter()
+            .map(|(pid, bucket)| (Pattern::from_id(*pid, word_len), self.node(bucket, depth)))
+            .collect()
+    }
+
+    /// Subtree for an already solved subproblem.
+    fn node(&mut self, cands: &[usize], depth: usize) -> TreeNode {
+        let (_, g) = self
+            .solve(cands, depth, usize::MAX)
+            .expect("subproblem was solved during the search");
+        let buckets = self.partition(g, cands);
+        TreeNode {
+            guess: self.dict.word(g).to_string(),
+            branches: self.branches(&buckets, depth - 1),
+        }
+    }
+
+    /// Candidates split by the feedback `g` gives them, sorted by pattern id.
+    /// The all-green bucket is left out: those answers are found by `g`.
+    fn partition(&self, g: usize, cands: &[usize]) -> Vec<(usize, Vec<usize>)> {
+        let table = self.dict.table();
+        let mut buckets: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &s in cands {
+            if s != g {
+                buckets.entry(table.get(g, s)).or_default().push(s);
+            }
+        }
+        let mut buckets: Vec<(usize, Vec<usize>)> = buckets.into_iter().collect();
+        buckets.sort_by_key(|&(pid, _)| pid);
+        buckets
+    }
+
+    /// Guesses worth trying for `cands`, most promising first.
+    fn ordered_guesses(&self, cands: &[usize]) -> Vec<usize> {
+        let table = self.dict.table();
+        let n = cands.len();
+        let mut counts = vec![0usize; num_patterns(self.dict.word_len())];
+        let mut scored: Vec<(usize, bool, usize)> = Vec::new();
+        for g in 0..self.dict.num_guesses() {
+            let mut sum_sq = 0;
+            let mut largest = 0;
+            for &s in cands {
+                let c = &mut counts[table.get(g, s)];
+                sum_sq += 2 * *c + 1;
+                *c += 1;
+                largest = largest.max(*c);
+            }
+            for &s in cands {
+                counts[table.get(g, s)] = 0;
+            }
+            let is_candidate = cands.binary_search(&g).is_ok();
+            // A probe that splits nothing cannot help.
+            if largest == n && !is_candidate {
+                continue;
+            }
+            scored.push((sum_sq, !is_candidate, g));
+        }
+        scored.sort_unstable();
+        if let Some(width) = self.width {
+            scored.truncate(width);
+        }
+        scored.into_iter().map(|(_, _, g)| g).collect()
+    }
+
+    /// Fewest total guesses to find each of `cands` within `depth` guesses, and
+    /// the first guess, if that beats `budget`.
+    fn solve(&mut self, cands: &[usize], depth: usize, budget: usize) -> Option<(usize, usize)> {
+        let n = cands.len();
+        if depth == 0 || lower_bound(n) >= budget {
+            return None;
+        }
+        match n {
+            1 => return Some((1, cands[0])),
+            _ if depth == 1 => return None,
+            2 => return Some((3, cands[0])),
+            _ => {}
+        }
+
+        let key = (cands.to_vec(), depth);
+        match self.memo.get(&key) {
+            Some(&Solved::Exact(cost, g)) => return (cost < budget).then_some((cost, g)),
+            Some(&Solved::AtLeast(bound)) if budget <= bound => return None,
+            _ => {}
+        }
+
+        let mut best = budget;
+        let mut best_guess = None;
+        for g in self.ordered_guesses(cands) {
+            let mut buckets = self.partition(g, cands);
+            let mut remaining: usize = buckets.iter().map(|(_, b)| lower_bound(b.len())).sum();
+            if n + remaining >= best {
+                continue;
+            }
+            // Largest buckets first, so hopeless guesses are cut early.
+            buckets.sort_by_key(|(_, b)| std::cmp::Reverse(b.len()));
+            let mut cost = n;
+            let mut feasible = true;
+            for (_, bucket) in &buckets {
+                remaining -= lower_bound(bucket.len());
+                let limit = best - cost - remaining;
+                match self.solve(bucket, depth - 1, limit) {
+                    Some((c, _)) => cost += c,
+                    None => {
+                        feasible = false;
+                        break;
+                    }
+                }
+            }
+            if feasible && cost < best {
+                best = cost;
+                best_guess = Some(g);
+            }
+        }
+
+        let result = best_guess.map(|g| (best, g));
+        self.memo.insert(
+            key,
+            match result {
+                Some((cost, g)) => Solved::Exact(cost, g),
+                None => Solved::AtLeast(budget),
+            },
+        );
+        result
+    }
+}
+
+/// Fewest total guesses any tree can spend on `n` candidates: one is guessed
+/// first, the others take at least two guesses each.
+fn lower_bound(n: usize) -> usize {
+    if n == 0 {
+        0
+    } else {
+        2 * n - 1
+    }
+}
+
+/// The subset of JSON that tree files use: objects, strings and numbers.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Object(Vec<(String, Json)>),
+    String(String),
+    Number(f64),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_usize(&self) -> Option<usize> {
+        match *self {
+            Json::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(text: &'a str) -> Self {
+        JsonParser {
+            chars: text.char_indices().peekable(),
+            text,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn error(&mut self, expected: &str) -> String {
+        match self.chars.peek() {
+            Some(&(i, c)) => format!("expected {} at byte {}, found '{}'", expected, i, c),
+            None => format!("expected {} at end of input", expected),
+        }
+    }
+
+    fn expect(&mut self, want: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next_if(|&(_, c)| c == want) {
+            Some(_) => Ok(()),
+            None => Err(self.error(&format!("'{}'", want))),
+        }
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("end of input")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.peek().map(|&(_, c)| c) {
+            Some('{') => self.object(),
+            Some('"') => self.string().map(Json::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("an object, string or number")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|&(_, c)| c == '}').is_some() {
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(Json::Object(fields)),
+                _ => return Err("expected ',' or '}' in object".to_string()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(s),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, c @ ('"' | '\\' | '/'))) => s.push(c),
+                    Some((_, 'n')) => s.push('\n'),
+                    Some((_, 't')) => s.push('\t'),
+                    _ => return Err("unsupported escape in string".to_string()),
+                },
+                Some((_, c)) => s.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.chars.peek().map_or(self.text.len(), |&(i, _)| i);
+        let mut end = start;
+        while let Some((i, c)) = self
+            .chars
+            .next_if(|&(_, c)| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            end = i + c.len_utf8();
+        }
+        self.text[start..end]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| format!("invalid number '{}'", &self.text[start..end]))
+    }
+}
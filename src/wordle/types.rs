@@ -1,63 +1,133 @@
+use crate::wordle::alphabet::Alphabet;
+use crate::wordle::candidates::CandidateSet;
+use crate::wordle::knowledge::Knowledge;
+use crate::wordle::pattern::Pattern;
 use std::collections::HashSet;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Guess {
     pub word: String,
-    pub feedback: Vec<char>,
+    pub feedback: Pattern,
 }
 
 impl Guess {
-    pub fn new(word: String, feedback: Vec<char>) -> Self {
+    pub fn new(word: String, feedback: Pattern) -> Self {
         Guess { word, feedback }
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct GameState {
-    pub candidates: Vec<String>,
+    /// Dictionary positions still consistent with every attempt.
+    pub candidates: CandidateSet,
     pub attempts: Vec<Guess>,
     pub attempt_count: usize,
     pub available_letters: HashSet<char>,
+    /// Letter-count and position constraints from every attempt.
+    pub knowledge: Knowledge,
+    /// Every guess must reuse the revealed hints.
+    pub hard_mode: bool,
 }
 
 impl GameState {
-    pub fn new(candidates: Vec<String>) -> Self {
-        let mut available_letters = HashSet::new();
-        for c in 'A'..='Z' {
-            available_letters.insert(c);
-        }
+    pub fn new(candidates: CandidateSet, alphabet: &Alphabet) -> Self {
+        let available_letters = alphabet.letters().iter().copied().collect();
 
         GameState {
             candidates,
             attempts: Vec::new(),
             attempt_count: 0,
             available_letters,
+            knowledge: Knowledge::new(),
+            hard_mode: false,
+        }
+    }
+
+    /// Why `word` is not a legal next guess in hard mode, if it isn't.
+    pub fn hard_mode_violation(&self, word: &str) -> Option<String> {
+        if self.hard_mode {
+            self.knowledge.hard_mode_violation(word)
+        } else {
+            None
         }
     }
 
+    /// Is `word` a legal next guess? Always true outside hard mode.
+    pub fn allows_guess(&self, word: &str) -> bool {
+        self.hard_mode_violation(word).is_none()
+    }
+
     pub fn add_attempt(&mut self, guess: Guess) {
-        // Update available letters based on feedback
-        for (i, &feedback_char) in guess.feedback.iter().enumerate() {
-            if feedback_char != 'G' && feedback_char != 'Y' {
-                // Gray letter - not in the word at all
-                if let Some(letter) = guess.word.chars().nth(i) {
-                    self.available_letters.remove(&letter);
-                }
-            }
+        // A gray tile only rules a letter out when no other tile confirmed it
+        self.knowledge.add(&guess);
+        for letter in self.knowledge.excluded_letters() {
+            self.available_letters.remove(&letter);
         }
 
         self.attempts.push(guess);
         self.attempt_count += 1;
     }
 
-    pub fn get_available_letters_sorted(&self) -> Vec<char> {
-        let mut letters: Vec<char> = self.available_letters.iter().cloned().collect();
-        letters.sort();
-        letters
+    /// Available letters in alphabet order (A to Z, then any extra letters).
+    pub fn get_available_letters_sorted(&self, alphabet: &Alphabet) -> Vec<char> {
+        alphabet
+            .letters()
+            .iter()
+            .copied()
+            .filter(|c| self.available_letters.contains(c))
+            .collect()
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FeedbackType {
     Green,
     Yellow,
     Black,
 }
+
+impl FeedbackType {
+    /// Base-3 digit used in pattern ids: 0 = gray, 1 = yellow, 2 = green.
+    pub fn trit(self) -> usize {
+        match self {
+            FeedbackType::Black => 0,
+            FeedbackType::Yellow => 1,
+            FeedbackType::Green => 2,
+        }
+    }
+
+    pub fn from_trit(trit: usize) -> Self {
+        match trit {
+            2 => FeedbackType::Green,
+            1 => FeedbackType::Yellow,
+            _ => FeedbackType::Black,
+        }
+    }
+
+    /// Accepts a letter (G/Y/B), an emoji tile (high-contrast orange and blue too)
+    /// or a trit digit.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'G' | '2' | '🟩' | '🟧' => Some(FeedbackType::Green),
+            'Y' | '1' | '🟨' | '🟦' => Some(FeedbackType::Yellow),
+            'B' | '0' | '⬜' | '⬛' => Some(FeedbackType::Black),
+            _ => None,
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            FeedbackType::Green => 'G',
+            FeedbackType::Yellow => 'Y',
+            FeedbackType::Black => 'B',
+        }
+    }
+
+    pub fn emoji(self) -> char {
+        match self {
+            FeedbackType::Green => '🟩',
+            FeedbackType::Yellow => '🟨',
+            FeedbackType::Black => '⬜',
+        }
+    }
+}